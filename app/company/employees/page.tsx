@@ -29,6 +29,7 @@ export default function EmployeesPage() {
   const [error, setError] = useState<string | null>(null);
   const [formWallet, setFormWallet] = useState('');
   const [formSalary, setFormSalary] = useState('');
+  const [formRangeProof, setFormRangeProof] = useState('');
 
   useEffect(() => {
     if (!connected) {
@@ -79,7 +80,7 @@ export default function EmployeesPage() {
 
   const handleAddEmployee = async (e: React.FormEvent<HTMLFormElement>) => {
     e.preventDefault();
-    if (!program || !publicKey || !formWallet || !formSalary) return;
+    if (!program || !publicKey || !formWallet || !formSalary || !formRangeProof) return;
 
     setIsEncrypting(true);
     setError(null);
@@ -87,6 +88,7 @@ export default function EmployeesPage() {
     try {
       // Validate wallet address
       const employeeWallet = new PublicKey(formWallet);
+      const salaryRangeProof = new PublicKey(formRangeProof);
       const salary = parseFloat(formSalary);
 
       // Mock encryption (Phase 3D will implement Arcium MPC)
@@ -114,6 +116,7 @@ export default function EmployeesPage() {
         employeeWallet,
         encryptedSalary,
         salaryCommitment,
+        salaryRangeProof,
         { monthly: {} }
       );
 
@@ -145,6 +148,7 @@ export default function EmployeesPage() {
       setShowAddModal(false);
       setFormWallet('');
       setFormSalary('');
+      setFormRangeProof('');
     } catch (err: any) {
       console.error('Error adding employee:', err);
       setError(err.message || 'Failed to add employee');
@@ -347,6 +351,23 @@ export default function EmployeesPage() {
                 <p className="text-xs text-slate-500 mt-1">Salary will be encrypted using Arcium MPC</p>
               </div>
 
+              <div>
+                <label className="block text-sm font-medium text-slate-300 mb-2">
+                  Salary Range Proof
+                </label>
+                <input
+                  type="text"
+                  name="salaryRangeProof"
+                  value={formRangeProof}
+                  onChange={(e) => setFormRangeProof((e.target as HTMLInputElement).value)}
+                  placeholder="Verified range proof context account"
+                  className="w-full px-4 py-2 bg-dark-bg border border-dark-border rounded-lg focus:outline-none focus:ring-2 focus:ring-ghost-500 text-white font-mono text-sm"
+                  required
+                  disabled={isEncrypting}
+                />
+                <p className="text-xs text-slate-500 mt-1">Proves the salary commitment opens to a 64-bit amount</p>
+              </div>
+
               {error && (
                 <div className="bg-red-500/10 border border-red-500/50 text-red-400 px-4 py-3 rounded-lg text-sm">
                   {error}
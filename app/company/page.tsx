@@ -124,6 +124,8 @@ export default function CompanyDashboard() {
                   // Convert payment frequency to enum
                   const frequency = paymentFrequency === '0' ? { weekly: {} } :
                                    paymentFrequency === '1' ? { biweekly: {} } :
+                                   paymentFrequency === '3' ? { semiMonthly: {} } :
+                                   paymentFrequency === '4' ? { quarterly: {} } :
                                    { monthly: {} };
 
                   const signature = await initializeCompany(
@@ -174,6 +176,8 @@ export default function CompanyDashboard() {
                     <option value="0">Weekly</option>
                     <option value="1">Bi-weekly</option>
                     <option value="2">Monthly</option>
+                    <option value="3">Semi-monthly (15th and last day)</option>
+                    <option value="4">Quarterly</option>
                   </select>
                 </div>
 
@@ -300,7 +304,9 @@ export default function CompanyDashboard() {
                   <span className="text-white font-medium">
                     {companyData?.paymentFrequency?.weekly ? 'Weekly' :
                      companyData?.paymentFrequency?.biweekly ? 'Bi-weekly' :
-                     companyData?.paymentFrequency?.monthly ? 'Monthly' : 'Loading...'}
+                     companyData?.paymentFrequency?.monthly ? 'Monthly' :
+                     companyData?.paymentFrequency?.semiMonthly ? 'Semi-monthly' :
+                     companyData?.paymentFrequency?.quarterly ? 'Quarterly' : 'Loading...'}
                   </span>
                 </div>
                 <div className="flex justify-between">
@@ -8,6 +8,7 @@ import WalletButton from '../../components/WalletButton';
 import Link from 'next/link';
 import { useGhostPayroll } from '../../hooks/useGhostPayroll';
 import { getCompanyPDA } from '../../lib/anchor/pdas';
+import { commitAmount } from '../../lib/anchor/commitment';
 
 interface Employee {
   walletAddress: string;
@@ -20,7 +21,7 @@ type PaymentStage = 'idle' | 'generating-proof' | 'processing' | 'complete' | 'e
 
 export default function PaymentsPage() {
   const { connected, publicKey } = useWallet();
-  const { program, processPayment, recordPaymentProof } = useGhostPayroll();
+  const { program, processPayment } = useGhostPayroll();
   const router = useRouter();
   const [employees, setEmployees] = useState<Employee[]>([]);
   const [paymentStage, setPaymentStage] = useState<PaymentStage>('idle');
@@ -93,28 +94,24 @@ export default function PaymentsPage() {
         // Mock: In Phase 3C, this will use ShadowWire for private transfers
         // For now, we'll use a direct payment with mock amount
         const mockAmount = BigInt(5000 * 1_000_000); // 5000 USDC (mock)
-        const amountCommitment = Array(32).fill(0); // Mock commitment
+        const amountCommitment = commitAmount(mockAmount);
 
         const signature = await processPayment(
           program,
           publicKey,
           new PublicKey(employee.walletAddress),
           mockAmount,
-          amountCommitment
+          mockAmount,
+          amountCommitment,
+          null, // Mock amounts are not bound to the salary commitment
+          { adjustment: {} }
         );
 
         console.log('Payment processed:', signature);
 
-        // Record payment proof on-chain
-        await recordPaymentProof(
-          program,
-          publicKey,
-          new PublicKey(employee.walletAddress),
-          BigInt(Date.now()),
-          amountCommitment,
-          [], // Mock ZK proof (will be from ShadowWire in Phase 3C)
-          signature
-        );
+        // Payment proofs are verified on-chain, so they are recorded once the
+        // ShadowWire prover (Phase 3C) produces a Groth16 proof of the transfer,
+        // against this payment's number and amountCommitment
 
         lastSignature = signature;
       }
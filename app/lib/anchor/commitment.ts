@@ -0,0 +1,104 @@
+import { ed25519, RistrettoPoint } from '@noble/curves/ed25519';
+import { blake3 } from '@noble/hashes/blake3';
+
+// Mirrors programs/ghost-payroll/src/commitment.rs byte for byte:
+// payment commitment = blake3(domain || amount as u64 little-endian || 32-byte nonce)
+// salary/budget commitment = amount * G + opening * H (Pedersen on Ristretto)
+
+export const AMOUNT_COMMITMENT_DOMAIN = new TextEncoder().encode(
+  'ghost-payroll/amount-commitment/v1'
+);
+
+// Pedersen generators of the ZK Token proof program (constants.rs)
+const PEDERSEN_G = RistrettoPoint.BASE;
+const PEDERSEN_H = RistrettoPoint.fromHex(
+  '8c9240b456a9e6dc65c377a1048d745f94a08cdb7f44cbcd7b46f34048871134'
+);
+
+export interface AmountCommitment {
+  commitment: number[];
+  nonce: number[];
+}
+
+export function randomNonce(): Uint8Array {
+  return crypto.getRandomValues(new Uint8Array(32));
+}
+
+export function commit(domain: Uint8Array, amount: bigint, nonce: Uint8Array): Uint8Array {
+  if (nonce.length !== 32) {
+    throw new Error('Commitment nonce must be 32 bytes');
+  }
+
+  const amountBytes = new Uint8Array(8);
+  new DataView(amountBytes.buffer).setBigUint64(0, amount, true);
+
+  const hasher = blake3.create({});
+  hasher.update(domain);
+  hasher.update(amountBytes);
+  hasher.update(nonce);
+  return hasher.digest();
+}
+
+// Commits to a payment amount; keep the nonce to open the commitment on chain
+export function commitAmount(amount: bigint, nonce: Uint8Array = randomNonce()): AmountCommitment {
+  return {
+    commitment: Array.from(commit(AMOUNT_COMMITMENT_DOMAIN, amount, nonce)),
+    nonce: Array.from(nonce),
+  };
+}
+
+function scalarToBytes(scalar: bigint): Uint8Array {
+  const bytes = new Uint8Array(32);
+  for (let i = 0; i < 32; i++) {
+    bytes[i] = Number((scalar >> BigInt(8 * i)) & 0xffn);
+  }
+  return bytes;
+}
+
+function bytesToScalar(bytes: Uint8Array): bigint {
+  return bytes.reduceRight((scalar, byte) => (scalar << 8n) | BigInt(byte), 0n);
+}
+
+// Uniformly random canonical scalar, encoded little-endian
+export function randomOpening(): Uint8Array {
+  const wide = crypto.getRandomValues(new Uint8Array(64));
+  return scalarToBytes(bytesToScalar(wide) % ed25519.CURVE.n);
+}
+
+export function pedersenCommit(amount: bigint, opening: Uint8Array): Uint8Array {
+  const r = bytesToScalar(opening);
+  if (opening.length !== 32 || r >= ed25519.CURVE.n) {
+    throw new Error('Pedersen opening must be a canonical 32-byte scalar');
+  }
+  return PEDERSEN_G.multiplyUnsafe(amount).add(PEDERSEN_H.multiplyUnsafe(r)).toRawBytes();
+}
+
+// Commitment to the sum of the committed amounts (openings add the same way)
+export function addCommitments(...commitments: number[][]): number[] {
+  const sum = commitments.reduce(
+    (total, commitment) => total.add(RistrettoPoint.fromHex(Uint8Array.from(commitment))),
+    RistrettoPoint.ZERO
+  );
+  return Array.from(sum.toRawBytes());
+}
+
+// Commitment to the difference of the committed amounts
+export function subtractCommitments(left: number[], right: number[]): number[] {
+  const difference = RistrettoPoint.fromHex(Uint8Array.from(left)).subtract(
+    RistrettoPoint.fromHex(Uint8Array.from(right))
+  );
+  return Array.from(difference.toRawBytes());
+}
+
+// Commits to an employee's full per-period salary (or a company budget);
+// on-cycle payments must supply the opening, so store it alongside the
+// encrypted salary
+export function commitSalary(
+  salary: bigint,
+  opening: Uint8Array = randomOpening()
+): AmountCommitment {
+  return {
+    commitment: Array.from(pedersenCommit(salary, opening)),
+    nonce: Array.from(opening),
+  };
+}
@@ -1,8 +1,33 @@
 import { Program } from '@coral-xyz/anchor';
 import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, Transaction } from '@solana/web3.js';
-import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress, createAssociatedTokenAccountInstruction } from '@solana/spl-token';
+import { TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddress, createAssociatedTokenAccountInstruction } from '@solana/spl-token';
 import { GhostPayroll } from '../../../target/types/ghost_payroll';
-import { getCompanyPDA, getTreasuryPDA, getEmployeePDA, getPaymentProofPDA } from './pdas';
+import {
+  getCompanyPDA,
+  getTreasuryPDA,
+  getTreasuryLedgerPDA,
+  getEmployeePDA,
+  getPaymentProofPDA,
+  getProtocolConfigPDA,
+  getFeeVaultPDA,
+  getAdvancePDA,
+  getVerifyingKeyPDA,
+} from './pdas';
+import { AmountCommitment } from './commitment';
+
+export type PaymentFrequency =
+  | { weekly: {} }
+  | { biweekly: {} }
+  | { monthly: {} }
+  | { semiMonthly: {} }
+  | { quarterly: {} };
+
+export type PaymentCategory =
+  | { salary: {} }
+  | { bonus: {} }
+  | { commission: {} }
+  | { reimbursement: {} }
+  | { adjustment: {} };
 
 export async function initializeCompany(
   program: Program<GhostPayroll>,
@@ -10,18 +35,21 @@ export async function initializeCompany(
   name: string,
   budgetCommitment: number[],
   paymentToken: PublicKey,
-  paymentFrequency: { weekly: {} } | { biweekly: {} } | { monthly: {} }
+  paymentFrequency: PaymentFrequency
 ) {
   const [companyPDA] = getCompanyPDA(authority);
-  const [treasuryPDA] = getTreasuryPDA(companyPDA);
+  const [treasuryPDA] = getTreasuryPDA(companyPDA, paymentToken);
+  const [treasuryLedgerPDA] = getTreasuryLedgerPDA(companyPDA, paymentToken);
 
   return await program.methods
     .initializeCompany(name, budgetCommitment, paymentFrequency)
     .accounts({
       company: companyPDA,
+      protocolConfig: getProtocolConfigPDA()[0],
       authority,
       paymentToken,
       treasury: treasuryPDA,
+      treasuryLedger: treasuryLedgerPDA,
       systemProgram: SystemProgram.programId,
       tokenProgram: TOKEN_PROGRAM_ID,
       rent: SYSVAR_RENT_PUBKEY,
@@ -35,7 +63,8 @@ export async function addEmployee(
   employeeWallet: PublicKey,
   encryptedSalary: number[],
   salaryCommitment: number[],
-  paymentFrequency: { weekly: {} } | { biweekly: {} } | { monthly: {} }
+  salaryRangeProof: PublicKey, // VerifyRangeProofU64 context account over salaryCommitment
+  paymentFrequency: PaymentFrequency
 ) {
   const [companyPDA] = getCompanyPDA(authority);
   const [employeePDA] = getEmployeePDA(companyPDA, employeeWallet);
@@ -131,15 +160,18 @@ export async function addEmployee(
   }
 
   // CRITICAL: Match EXACTLY the IDL
-  // IDL accounts: company, employee, authority, employee_token_account, system_program
+  // IDL accounts: company, protocol_config, employee, authority, employee_token_account, system_program
   // IDL args: employee_wallet, encrypted_salary, salary_commitment, payment_frequency
   const tx = await program.methods
     .addEmployee(employeeWallet, encryptedSalaryBuffer, salaryCommitment, paymentFrequency)
     .accounts({
       company: companyPDA,
+      protocolConfig: getProtocolConfigPDA()[0],
       employee: employeePDA,
       authority: authority,
       employeeTokenAccount: employeeTokenAccount,
+      treasuryLedger: getTreasuryLedgerPDA(companyPDA, paymentToken)[0],
+      salaryRangeProof,
       systemProgram: SystemProgram.programId,
     } as any)
     .rpc();
@@ -152,28 +184,113 @@ export async function processPayment(
   authority: PublicKey,
   employeeWallet: PublicKey,
   amount: bigint,
-  amountCommitment: number[]
+  fullPeriodAmount: bigint,
+  amountCommitment: AmountCommitment,
+  salaryNonce: number[] | null,
+  category: PaymentCategory = { salary: {} },
+  payrollRun?: PublicKey
 ) {
+  const withheld = 'salary' in category || 'bonus' in category || 'commission' in category;
+
   const [companyPDA] = getCompanyPDA(authority);
   const [employeePDA] = getEmployeePDA(companyPDA, employeeWallet);
-  const [treasuryPDA] = getTreasuryPDA(companyPDA);
 
-  const paymentToken = await getPaymentToken(program, companyPDA);
+  const employee = await program.account.employee.fetch(employeePDA);
+  const [treasuryPDA] = getTreasuryPDA(companyPDA, employee.payoutMint);
   const employeeTokenAccount = await getAssociatedTokenAddress(
-    paymentToken,
+    employee.payoutMint,
     employeeWallet
   );
 
   return await program.methods
-    .processPayment(amount, amountCommitment)
+    .processPayment(
+      amount,
+      fullPeriodAmount,
+      amountCommitment.commitment,
+      amountCommitment.nonce,
+      salaryNonce,
+      category
+    )
     .accounts({
       company: companyPDA,
       employee: employeePDA,
       treasury: treasuryPDA,
+      treasuryLedger: getTreasuryLedgerPDA(companyPDA, employee.payoutMint)[0],
+      protocolConfig: getProtocolConfigPDA()[0],
+      feeVault: getFeeVaultPDA(employee.payoutMint)[0],
+      payrollRun: payrollRun ?? null,
+      advance: employee.hasAdvance ? getAdvancePDA(employeePDA)[0] : null,
       employeeTokenAccount,
+      mint: employee.payoutMint,
       authority,
       tokenProgram: TOKEN_PROGRAM_ID,
     })
+    .remainingAccounts(
+      withheld
+        ? employee.withholdingLines.map((line) => ({
+            pubkey: line.vault,
+            isWritable: true,
+            isSigner: false,
+          }))
+        : []
+    )
+    .rpc();
+}
+
+// Split proofs of a confidential transfer, each pre-verified into a proof
+// context account
+export type ConfidentialTransferProofs = {
+  equalityProof: PublicKey; // VerifyCiphertextCommitmentEquality
+  ciphertextValidityProof: PublicKey; // VerifyBatchedGroupedCiphertext2HandlesValidity
+  rangeProof: PublicKey; // VerifyBatchedRangeProofU128
+};
+
+// Pays from a confidential Token-2022 treasury. The amount is only encrypted
+// inside the transfer proofs; salary is bound to the employee's salary
+// commitment by `salaryOpeningDifference`.
+export async function processConfidentialPayment(
+  program: Program<GhostPayroll>,
+  authority: PublicKey,
+  employeeWallet: PublicKey,
+  proofs: ConfidentialTransferProofs,
+  newSourceDecryptableBalance: number[], // treasury's AE-encrypted balance after the payout (36 bytes)
+  sourceDecryptHandles: number[], // treasury's handles for the amount's low and high ciphertexts (64 bytes)
+  salaryOpeningDifference: number[] | null, // transfer amount's combined opening minus the salary's (salary only)
+  category: PaymentCategory,
+  payrollRun?: PublicKey
+) {
+  const [companyPDA] = getCompanyPDA(authority);
+  const [employeePDA] = getEmployeePDA(companyPDA, employeeWallet);
+
+  const employee = await program.account.employee.fetch(employeePDA);
+  const employeeTokenAccount = await getAssociatedTokenAddress(
+    employee.payoutMint,
+    employeeWallet,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+
+  return await program.methods
+    .processConfidentialPayment(
+      newSourceDecryptableBalance,
+      sourceDecryptHandles,
+      salaryOpeningDifference,
+      category
+    )
+    .accounts({
+      company: companyPDA,
+      employee: employeePDA,
+      treasury: getTreasuryPDA(companyPDA, employee.payoutMint)[0],
+      protocolConfig: getProtocolConfigPDA()[0],
+      employeeTokenAccount,
+      mint: employee.payoutMint,
+      payrollRun: payrollRun ?? null,
+      equalityProof: proofs.equalityProof,
+      ciphertextValidityProof: proofs.ciphertextValidityProof,
+      rangeProof: proofs.rangeProof,
+      authority,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    })
     .rpc();
 }
 
@@ -181,9 +298,9 @@ export async function recordPaymentProof(
   program: Program<GhostPayroll>,
   authority: PublicKey,
   employeeWallet: PublicKey,
-  paymentId: bigint,
-  amountCommitment: number[],
-  zkProof: number[],
+  paymentId: bigint, // payment number of the employee's latest payment
+  amountCommitment: number[], // commitment that payment was made with
+  zkProof: number[], // Groth16 proof, a || b || c (256 bytes)
   shadowwireTxSignature: string
 ) {
   const [companyPDA] = getCompanyPDA(authority);
@@ -194,7 +311,9 @@ export async function recordPaymentProof(
     .recordPaymentProof(paymentId, amountCommitment, zkProof, shadowwireTxSignature)
     .accounts({
       company: companyPDA,
+      protocolConfig: getProtocolConfigPDA()[0],
       employee: employeePDA,
+      verifyingKey: getVerifyingKeyPDA(companyPDA)[0],
       paymentProof: paymentProofPDA,
       authority,
       systemProgram: SystemProgram.programId,
@@ -202,6 +321,33 @@ export async function recordPaymentProof(
     .rpc();
 }
 
+export async function fundTreasury(
+  program: Program<GhostPayroll>,
+  companyAuthority: PublicKey,
+  funder: PublicKey,
+  mint: PublicKey,
+  amount: bigint
+) {
+  const [companyPDA] = getCompanyPDA(companyAuthority);
+  const [treasuryPDA] = getTreasuryPDA(companyPDA, mint);
+  const [treasuryLedgerPDA] = getTreasuryLedgerPDA(companyPDA, mint);
+  const funderTokenAccount = await getAssociatedTokenAddress(mint, funder);
+
+  return await program.methods
+    .fundTreasury(amount)
+    .accounts({
+      company: companyPDA,
+      protocolConfig: getProtocolConfigPDA()[0],
+      treasury: treasuryPDA,
+      treasuryLedger: treasuryLedgerPDA,
+      funderTokenAccount,
+      mint,
+      funder,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+}
+
 // Helper to get payment token from company account
 async function getPaymentToken(
   program: Program<GhostPayroll>,
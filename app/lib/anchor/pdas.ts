@@ -8,9 +8,22 @@ export function getCompanyPDA(authority: PublicKey): [PublicKey, number] {
   );
 }
 
-export function getTreasuryPDA(companyPDA: PublicKey): [PublicKey, number] {
+export function getTreasuryPDA(
+  companyPDA: PublicKey,
+  mint: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('treasury'), companyPDA.toBuffer(), mint.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+export function getTreasuryLedgerPDA(
+  companyPDA: PublicKey,
+  mint: PublicKey
+): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from('treasury'), companyPDA.toBuffer()],
+    [Buffer.from('treasury_ledger'), companyPDA.toBuffer(), mint.toBuffer()],
     PROGRAM_ID
   );
 }
@@ -47,3 +60,82 @@ export function getPaymentProofPDA(
     PROGRAM_ID
   );
 }
+
+export function getWithdrawalPDA(
+  companyPDA: PublicKey,
+  withdrawalId: bigint
+): [PublicKey, number] {
+  const buffer = Buffer.alloc(8);
+  buffer.writeBigUInt64LE(withdrawalId);
+
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('withdrawal'), companyPDA.toBuffer(), buffer],
+    PROGRAM_ID
+  );
+}
+
+export function getProtocolConfigPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('protocol_config')],
+    PROGRAM_ID
+  );
+}
+
+export function getFeeVaultPDA(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('fee_vault'), mint.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+export function getPaymentCorrectionPDA(paymentProofPDA: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('payment_correction'), paymentProofPDA.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+export function getPayrollRunPDA(companyPDA: PublicKey, period: bigint): [PublicKey, number] {
+  const buffer = Buffer.alloc(8);
+  buffer.writeBigUInt64LE(period);
+
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('payroll_run'), companyPDA.toBuffer(), buffer],
+    PROGRAM_ID
+  );
+}
+
+export function getPayrollPlanPDA(
+  companyPDA: PublicKey,
+  employeeWallet: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('payroll_plan'), companyPDA.toBuffer(), employeeWallet.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+export function getAdvancePDA(employeePDA: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('advance'), employeePDA.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+export function getWithholdingVaultPDA(
+  companyPDA: PublicKey,
+  mint: PublicKey,
+  name: string
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('withholding_vault'), companyPDA.toBuffer(), mint.toBuffer(), Buffer.from(name)],
+    PROGRAM_ID
+  );
+}
+
+export function getVerifyingKeyPDA(companyPDA: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('verifying_key'), companyPDA.toBuffer()],
+    PROGRAM_ID
+  );
+}
@@ -0,0 +1,300 @@
+//! Proleptic Gregorian calendar arithmetic on Unix timestamps (UTC).
+//!
+//! Day/civil conversions follow Howard Hinnant's `days_from_civil` /
+//! `civil_from_days` algorithms, which are exact for every representable date.
+
+use crate::constants::*;
+
+/// A calendar date
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+pub fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        _ if is_leap_year(year) => 29,
+        _ => 28,
+    }
+}
+
+/// Days since 1970-01-01 for a calendar date
+pub fn days_from_civil(date: Date) -> i64 {
+    let year = if date.month <= 2 { date.year - 1 } else { date.year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = date.month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+        + date.day as i64
+        - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Calendar date for a count of days since 1970-01-01
+pub fn civil_from_days(days: i64) -> Date {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    Date { year, month, day }
+}
+
+/// Splits a timestamp into its calendar date and seconds into the day
+pub fn to_date(timestamp: i64) -> (Date, i64) {
+    (
+        civil_from_days(timestamp.div_euclid(SECONDS_PER_DAY)),
+        timestamp.rem_euclid(SECONDS_PER_DAY),
+    )
+}
+
+/// Timestamp of `date` at `time_of_day` seconds past midnight
+pub fn to_timestamp(date: Date, time_of_day: i64) -> i64 {
+    days_from_civil(date) * SECONDS_PER_DAY + time_of_day
+}
+
+/// Months since year 0 (used to step across month boundaries)
+fn month_index(year: i64, month: u32) -> i64 {
+    year * 12 + month as i64 - 1
+}
+
+/// Date `day` of the month at `index`, clamped to the month's last day
+fn clamped_date(index: i64, day: u32) -> Date {
+    let year = index.div_euclid(12);
+    let month = (index.rem_euclid(12) + 1) as u32;
+    Date {
+        year,
+        month,
+        day: day.min(days_in_month(year, month)),
+    }
+}
+
+/// First occurrence strictly after `after` of a schedule paying every
+/// `period` seconds starting at `anchor`
+pub fn next_fixed_interval(anchor: i64, period: i64, after: i64) -> i64 {
+    if after < anchor {
+        return anchor;
+    }
+    anchor + ((after - anchor) / period + 1) * period
+}
+
+/// First occurrence strictly after `after` of a schedule paying every
+/// `step` months on the anchor's day-of-month (clamped for short months)
+/// and time of day, starting at `anchor`
+pub fn next_monthly(anchor: i64, step: u32, after: i64) -> i64 {
+    if after < anchor {
+        return anchor;
+    }
+    let (anchor_date, time_of_day) = to_date(anchor);
+    let (after_date, _) = to_date(after);
+    let anchor_index = month_index(anchor_date.year, anchor_date.month);
+    let after_index = month_index(after_date.year, after_date.month);
+    let step = step as i64;
+
+    let mut k = (after_index - anchor_index) / step;
+    loop {
+        let candidate = to_timestamp(
+            clamped_date(anchor_index + k * step, anchor_date.day),
+            time_of_day,
+        );
+        if candidate > after {
+            return candidate;
+        }
+        k += 1;
+    }
+}
+
+/// First occurrence strictly after `after` of a schedule paying on the 15th
+/// and the last day of every month at the anchor's time of day, starting no
+/// earlier than `anchor`
+pub fn next_semi_monthly(anchor: i64, after: i64) -> i64 {
+    let after = after.max(anchor - 1);
+    let (_, time_of_day) = to_date(anchor);
+    let (after_date, _) = to_date(after);
+    let mut index = month_index(after_date.year, after_date.month);
+    loop {
+        for day in [15, 31] {
+            let candidate = to_timestamp(clamped_date(index, day), time_of_day);
+            if candidate > after {
+                return candidate;
+            }
+        }
+        index += 1;
+    }
+}
+
+/// Last occurrence strictly before `before` of the fixed-interval schedule,
+/// extending the schedule backwards past its anchor if needed
+pub fn prev_fixed_interval(anchor: i64, period: i64, before: i64) -> i64 {
+    anchor + (before - anchor - 1).div_euclid(period) * period
+}
+
+/// Last occurrence strictly before `before` of the monthly schedule,
+/// extending the schedule backwards past its anchor if needed
+pub fn prev_monthly(anchor: i64, step: u32, before: i64) -> i64 {
+    let (anchor_date, time_of_day) = to_date(anchor);
+    let (before_date, _) = to_date(before);
+    let anchor_index = month_index(anchor_date.year, anchor_date.month);
+    let before_index = month_index(before_date.year, before_date.month);
+    let step = step as i64;
+
+    let mut k = (before_index - anchor_index).div_euclid(step);
+    loop {
+        let candidate = to_timestamp(
+            clamped_date(anchor_index + k * step, anchor_date.day),
+            time_of_day,
+        );
+        if candidate < before {
+            return candidate;
+        }
+        k -= 1;
+    }
+}
+
+/// Last occurrence strictly before `before` of the semi-monthly schedule
+pub fn prev_semi_monthly(anchor: i64, before: i64) -> i64 {
+    let (_, time_of_day) = to_date(anchor);
+    let (before_date, _) = to_date(before);
+    let mut index = month_index(before_date.year, before_date.month);
+    loop {
+        for day in [31, 15] {
+            let candidate = to_timestamp(clamped_date(index, day), time_of_day);
+            if candidate < before {
+                return candidate;
+            }
+        }
+        index -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(year: i64, month: u32, day: u32) -> i64 {
+        to_timestamp(Date { year, month, day }, 0)
+    }
+
+    #[test]
+    fn civil_round_trip() {
+        assert_eq!(days_from_civil(Date { year: 1970, month: 1, day: 1 }), 0);
+        assert_eq!(days_from_civil(Date { year: 2000, month: 3, day: 1 }), 11_017);
+        assert_eq!(civil_from_days(-1), Date { year: 1969, month: 12, day: 31 });
+
+        for days in -800_000..800_000 {
+            assert_eq!(days_from_civil(civil_from_days(days)), days);
+        }
+    }
+
+    #[test]
+    fn timestamps_match_known_dates() {
+        // 2024-02-29T12:00:00Z
+        let (date, time_of_day) = to_date(1_709_208_000);
+        assert_eq!(date, Date { year: 2024, month: 2, day: 29 });
+        assert_eq!(time_of_day, 12 * 3_600);
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(is_leap_year(2024));
+        assert!(is_leap_year(2000));
+        assert!(!is_leap_year(1900));
+        assert!(!is_leap_year(2023));
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2100, 2), 28);
+    }
+
+    #[test]
+    fn monthly_clamps_to_short_months() {
+        let anchor = ts(2024, 1, 31);
+        assert_eq!(next_monthly(anchor, 1, anchor), ts(2024, 2, 29));
+        assert_eq!(next_monthly(anchor, 1, ts(2024, 2, 29)), ts(2024, 3, 31));
+        assert_eq!(next_monthly(anchor, 1, ts(2025, 2, 1)), ts(2025, 2, 28));
+        assert_eq!(next_monthly(anchor, 1, ts(2024, 4, 30)), ts(2024, 5, 31));
+    }
+
+    #[test]
+    fn monthly_does_not_drift() {
+        let anchor = ts(2024, 1, 15);
+        let mut due = anchor;
+        for _ in 0..12 {
+            due = next_monthly(anchor, 1, due);
+        }
+        assert_eq!(due, ts(2025, 1, 15));
+    }
+
+    #[test]
+    fn quarterly_steps_three_months() {
+        let anchor = ts(2024, 1, 31);
+        assert_eq!(next_monthly(anchor, 3, anchor), ts(2024, 4, 30));
+        assert_eq!(next_monthly(anchor, 3, ts(2024, 4, 30)), ts(2024, 7, 31));
+        assert_eq!(next_monthly(anchor, 3, ts(2024, 5, 1)), ts(2024, 7, 31));
+    }
+
+    #[test]
+    fn semi_monthly_pays_on_15th_and_last_day() {
+        let anchor = ts(2024, 1, 1);
+        assert_eq!(next_semi_monthly(anchor, anchor), ts(2024, 1, 15));
+        assert_eq!(next_semi_monthly(anchor, ts(2024, 1, 15)), ts(2024, 1, 31));
+        assert_eq!(next_semi_monthly(anchor, ts(2024, 1, 31)), ts(2024, 2, 15));
+        assert_eq!(next_semi_monthly(anchor, ts(2024, 2, 15)), ts(2024, 2, 29));
+        assert_eq!(next_semi_monthly(anchor, ts(2023, 2, 15)), ts(2024, 1, 15));
+    }
+
+    #[test]
+    fn future_anchor_is_first_payment() {
+        let anchor = ts(2025, 6, 1);
+        let now = ts(2025, 1, 1);
+        assert_eq!(next_monthly(anchor, 1, now), anchor);
+        assert_eq!(next_fixed_interval(anchor, SECONDS_PER_WEEK, now), anchor);
+        assert_eq!(next_semi_monthly(anchor, now), ts(2025, 6, 15));
+    }
+
+    #[test]
+    fn fixed_interval_keeps_anchor_phase() {
+        let anchor = ts(2024, 1, 5);
+        assert_eq!(next_fixed_interval(anchor, SECONDS_PER_WEEK, anchor), ts(2024, 1, 12));
+        assert_eq!(
+            next_fixed_interval(anchor, SECONDS_PER_BIWEEK, ts(2024, 1, 20)),
+            ts(2024, 2, 2)
+        );
+    }
+
+    #[test]
+    fn previous_payday_inverts_next() {
+        let anchor = ts(2024, 1, 31);
+        for due in [ts(2024, 2, 29), ts(2024, 3, 31), ts(2025, 2, 28)] {
+            let prev = prev_monthly(anchor, 1, due);
+            assert_eq!(next_monthly(anchor, 1, prev), due);
+        }
+        assert_eq!(prev_monthly(anchor, 1, anchor), ts(2023, 12, 31));
+        assert_eq!(prev_monthly(anchor, 3, ts(2024, 4, 30)), anchor);
+
+        assert_eq!(prev_semi_monthly(anchor, ts(2024, 3, 15)), ts(2024, 2, 29));
+        assert_eq!(prev_semi_monthly(anchor, ts(2024, 3, 31)), ts(2024, 3, 15));
+
+        let weekly_anchor = ts(2024, 1, 5);
+        assert_eq!(
+            prev_fixed_interval(weekly_anchor, SECONDS_PER_WEEK, ts(2024, 1, 12)),
+            weekly_anchor
+        );
+        assert_eq!(
+            prev_fixed_interval(weekly_anchor, SECONDS_PER_WEEK, weekly_anchor),
+            ts(2023, 12, 29)
+        );
+    }
+}
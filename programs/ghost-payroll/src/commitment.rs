@@ -0,0 +1,373 @@
+//! Hash commitments to payment amounts and Pedersen commitments to salaries
+//! and budgets.
+//!
+//! A payment commitment is `blake3(domain || amount_le_u64 || nonce)`, where
+//! `domain` is a fixed tag separating commitment types and `nonce` is 32
+//! random bytes that blind the amount. Every field is fixed-length, so the
+//! encoding is unambiguous.
+//!
+//! Salary and budget commitments are Pedersen commitments on Ristretto,
+//! `amount * G + opening * H`, over the generators of the ZK Token proof
+//! program. They add homomorphically, and that program's range proofs apply
+//! to them directly. An opening is a canonical little-endian scalar.
+//!
+//! `app/lib/anchor/commitment.ts` produces identical bytes for both.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::{
+    curve25519::{
+        ristretto::{
+            add_ristretto, multiscalar_multiply_ristretto, subtract_ristretto, validate_ristretto,
+            PodRistrettoPoint,
+        },
+        scalar::PodScalar,
+    },
+    instruction::{
+        BatchedGroupedCiphertext2HandlesValidityProofContext, ProofType, RangeProofContext,
+    },
+    zk_token_proof_state::ProofContextState,
+};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+/// Commitment to `amount` under `domain`, blinded by `nonce`
+pub fn commit(domain: &[u8], amount: u64, nonce: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(domain);
+    hasher.update(&amount.to_le_bytes());
+    hasher.update(nonce);
+    *hasher.finalize().as_bytes()
+}
+
+/// Commitment to a payment amount
+pub fn commit_amount(amount: u64, nonce: &[u8; 32]) -> [u8; 32] {
+    commit(AMOUNT_COMMITMENT_DOMAIN, amount, nonce)
+}
+
+/// Fails unless `amount` and `nonce` open the payment amount `commitment`
+pub fn verify_amount_commitment(commitment: &[u8; 32], amount: u64, nonce: &[u8; 32]) -> Result<()> {
+    require!(
+        commit_amount(amount, nonce) == *commitment,
+        GhostPayrollError::InvalidAmountCommitment
+    );
+    Ok(())
+}
+
+/// Pedersen commitment to `amount` under `opening`
+pub fn pedersen_commit(amount: u64, opening: &[u8; 32]) -> Result<[u8; 32]> {
+    let mut amount_scalar = [0u8; 32];
+    amount_scalar[..8].copy_from_slice(&amount.to_le_bytes());
+    multiscalar_multiply_ristretto(
+        &[PodScalar(amount_scalar), PodScalar(*opening)],
+        &[PodRistrettoPoint(PEDERSEN_G), PodRistrettoPoint(PEDERSEN_H)],
+    )
+    .map(|point| point.0)
+    .ok_or_else(|| error!(GhostPayrollError::InvalidPedersenCommitment))
+}
+
+/// Fails unless `commitment` is a valid Pedersen commitment
+pub fn validate_pedersen_commitment(commitment: &[u8; 32]) -> Result<()> {
+    require!(
+        validate_ristretto(&PodRistrettoPoint(*commitment)),
+        GhostPayrollError::InvalidPedersenCommitment
+    );
+    Ok(())
+}
+
+/// Fails unless `range_proof_context` holds a verified `RangeProofU64` over
+/// `commitment`, proving it commits to a value in [0, 2^64). The caller checks
+/// that the account is owned by the proof program, which only writes context
+/// state for proofs that verified.
+pub fn verify_range_proof(range_proof_context: &AccountInfo, commitment: &[u8; 32]) -> Result<()> {
+    let data = range_proof_context.try_borrow_data()?;
+    let proof_state = ProofContextState::<RangeProofContext>::try_from_bytes(&data)
+        .map_err(|_| GhostPayrollError::InvalidRangeProof)?;
+    require!(
+        matches!(
+            ProofType::try_from(proof_state.proof_type),
+            Ok(ProofType::RangeProofU64)
+        ),
+        GhostPayrollError::InvalidRangeProof
+    );
+    require!(
+        proof_state.proof_context.commitment.0 == *commitment,
+        GhostPayrollError::RangeProofMismatch
+    );
+    Ok(())
+}
+
+/// Commitment to the sum of the amounts committed by `left` and `right`
+pub fn add_commitments(left: &[u8; 32], right: &[u8; 32]) -> Result<[u8; 32]> {
+    add_ristretto(&PodRistrettoPoint(*left), &PodRistrettoPoint(*right))
+        .map(|point| point.0)
+        .ok_or_else(|| error!(GhostPayrollError::InvalidPedersenCommitment))
+}
+
+/// Commitment to the difference of the amounts committed by `left` and `right`
+pub fn subtract_commitments(left: &[u8; 32], right: &[u8; 32]) -> Result<[u8; 32]> {
+    subtract_ristretto(&PodRistrettoPoint(*left), &PodRistrettoPoint(*right))
+        .map(|point| point.0)
+        .ok_or_else(|| error!(GhostPayrollError::InvalidPedersenCommitment))
+}
+
+/// Fails unless `salary` and `opening` open the employee's `salary_commitment`
+pub fn verify_salary_commitment(commitment: &[u8; 32], salary: u64, opening: &[u8; 32]) -> Result<()> {
+    require!(
+        pedersen_commit(salary, opening).ok() == Some(*commitment),
+        GhostPayrollError::SalaryCommitmentMismatch
+    );
+    Ok(())
+}
+
+/// Fails unless an on-cycle payment's `full_period_amount` opens the
+/// employee's salary commitment; off-cycle categories are not bound to it
+pub fn verify_salary_payment(
+    salary_commitment: &[u8; 32],
+    category: PaymentCategory,
+    full_period_amount: u64,
+    salary_nonce: Option<[u8; 32]>,
+) -> Result<()> {
+    if category.is_off_cycle() {
+        return Ok(());
+    }
+    let salary_nonce = salary_nonce.ok_or(GhostPayrollError::SalaryCommitmentMismatch)?;
+    verify_salary_commitment(salary_commitment, full_period_amount, &salary_nonce)
+}
+
+/// Fails unless the transfer amount in `ciphertext_validity_proof`, a
+/// verified `BatchedGroupedCiphertext2HandlesValidity` context from a split
+/// confidential transfer proof, is the amount `commitment` commits to. The
+/// commitments to the amount's low and high bits are recombined first;
+/// `opening_difference` is their combined opening minus the commitment's, so
+/// revealing it says nothing about the amount. The caller checks that the
+/// account is owned by the proof program.
+pub fn verify_transfer_amount(
+    ciphertext_validity_proof: &AccountInfo,
+    commitment: &[u8; 32],
+    opening_difference: &[u8; 32],
+) -> Result<()> {
+    let data = ciphertext_validity_proof.try_borrow_data()?;
+    let proof_state =
+        ProofContextState::<BatchedGroupedCiphertext2HandlesValidityProofContext>::try_from_bytes(
+            &data,
+        )
+        .map_err(|_| GhostPayrollError::InvalidTransferProof)?;
+    require!(
+        matches!(
+            ProofType::try_from(proof_state.proof_type),
+            Ok(ProofType::BatchedGroupedCiphertext2HandlesValidity)
+        ),
+        GhostPayrollError::InvalidTransferProof
+    );
+
+    // A grouped ciphertext starts with the Pedersen commitment its handles share
+    let context = &proof_state.proof_context;
+    let mut commitment_lo = [0u8; 32];
+    commitment_lo.copy_from_slice(&context.grouped_ciphertext_lo.0[..32]);
+    let mut commitment_hi = [0u8; 32];
+    commitment_hi.copy_from_slice(&context.grouped_ciphertext_hi.0[..32]);
+
+    let mut one = [0u8; 32];
+    one[0] = 1;
+    let mut hi_shift = [0u8; 32];
+    hi_shift[..8].copy_from_slice(&(1u64 << CONFIDENTIAL_TRANSFER_LO_BITS).to_le_bytes());
+    let amount_commitment = multiscalar_multiply_ristretto(
+        &[PodScalar(one), PodScalar(hi_shift)],
+        &[PodRistrettoPoint(commitment_lo), PodRistrettoPoint(commitment_hi)],
+    )
+    .ok_or(GhostPayrollError::InvalidTransferProof)?;
+    let expected = multiscalar_multiply_ristretto(
+        &[PodScalar(one), PodScalar(*opening_difference)],
+        &[PodRistrettoPoint(*commitment), PodRistrettoPoint(PEDERSEN_H)],
+    )
+    .ok_or(GhostPayrollError::SalaryCommitmentMismatch)?;
+
+    require!(
+        amount_commitment.0 == expected.0,
+        GhostPayrollError::SalaryCommitmentMismatch
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::{
+        encryption::{
+            elgamal::ElGamalKeypair,
+            grouped_elgamal::GroupedElGamal,
+            pedersen::{self, Pedersen, PedersenCommitment, PedersenOpening},
+        },
+        instruction::{
+            BatchedGroupedCiphertext2HandlesValidityProofData, RangeProofU64Data, ZkProofData,
+        },
+        zk_token_proof_program,
+    };
+
+    /// Shared with the TypeScript helper's test so both sides stay byte-identical
+    #[test]
+    fn matches_off_chain_vector() {
+        let expected = [
+            0x4a, 0x8f, 0x10, 0x38, 0x1f, 0x96, 0x26, 0x6b, 0xc9, 0x19, 0x98, 0x26, 0x1f, 0x58,
+            0x25, 0x6f, 0xae, 0x37, 0x3e, 0x63, 0xce, 0xea, 0x33, 0x32, 0x0d, 0x70, 0x3c, 0x75,
+            0x13, 0xcc, 0x91, 0x59,
+        ];
+        assert_eq!(commit_amount(5_000_000_000, &[7u8; 32]), expected);
+    }
+
+    #[test]
+    fn binds_amount_nonce_and_domain() {
+        let nonce = [7u8; 32];
+        let commitment = commit_amount(1_000_000, &nonce);
+        assert!(verify_amount_commitment(&commitment, 1_000_000, &nonce).is_ok());
+        assert!(verify_amount_commitment(&commitment, 1_000_001, &nonce).is_err());
+        assert!(verify_amount_commitment(&commitment, 1_000_000, &[8u8; 32]).is_err());
+        assert_ne!(commit(b"other-domain", 1_000_000, &nonce), commitment);
+    }
+
+    /// Pedersen generators must match the ZK Token proof program's
+    #[test]
+    fn pedersen_generators_match_proof_program() {
+        assert_eq!(PEDERSEN_G, pedersen::G.compress().to_bytes());
+        assert_eq!(PEDERSEN_H, pedersen::H.compress().to_bytes());
+
+        let opening = PedersenOpening::new_rand();
+        let expected = Pedersen::with(1_000_000u64, &opening);
+        assert_eq!(
+            pedersen_commit(1_000_000, opening.as_bytes()).unwrap(),
+            expected.to_bytes()
+        );
+    }
+
+    #[test]
+    fn pedersen_commitments_add_homomorphically() {
+        let (first, second) = (PedersenOpening::new_rand(), PedersenOpening::new_rand());
+        let total = add_commitments(
+            &pedersen_commit(3_000, first.as_bytes()).unwrap(),
+            &pedersen_commit(4_000, second.as_bytes()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(total, pedersen_commit(7_000, (&first + &second).as_bytes()).unwrap());
+
+        let identity = [0u8; 32];
+        assert_eq!(add_commitments(&identity, &total).unwrap(), total);
+        assert_eq!(subtract_commitments(&total, &total).unwrap(), identity);
+
+        assert!(pedersen_commit(1, &[0xff; 32]).is_err());
+        assert!(validate_pedersen_commitment(&[0xff; 32]).is_err());
+    }
+
+    /// A range proof over the budget headroom proves the payroll fits the budget
+    #[test]
+    fn headroom_range_proof_checks_budget() {
+        let budget_opening = PedersenOpening::new_rand();
+        let salary_opening = PedersenOpening::new_rand();
+        let budget = pedersen_commit(10_000, budget_opening.as_bytes()).unwrap();
+        let salaries = pedersen_commit(7_500, salary_opening.as_bytes()).unwrap();
+
+        let headroom = subtract_commitments(&budget, &salaries).unwrap();
+        let headroom_opening = &budget_opening - &salary_opening;
+        let proof = RangeProofU64Data::new(
+            &PedersenCommitment::from_bytes(&headroom).unwrap(),
+            2_500,
+            &headroom_opening,
+        )
+        .unwrap();
+        assert!(proof.verify_proof().is_ok());
+        assert_eq!(proof.context_data().commitment.0, headroom);
+
+        // Over budget, the headroom wraps below zero and no 64-bit proof exists
+        let overspend = subtract_commitments(&salaries, &budget).unwrap();
+        let forged = RangeProofU64Data::new(
+            &PedersenCommitment::from_bytes(&overspend).unwrap(),
+            2_500,
+            &(&salary_opening - &budget_opening),
+        )
+        .unwrap();
+        assert!(forged.verify_proof().is_err());
+    }
+
+    /// A salary commitment is accepted only with a range proof context over it
+    #[test]
+    fn range_proof_context_must_cover_commitment() {
+        let opening = PedersenOpening::new_rand();
+        let salary = pedersen_commit(5_000, opening.as_bytes()).unwrap();
+        let other = pedersen_commit(5_000, PedersenOpening::new_rand().as_bytes()).unwrap();
+        let proof = RangeProofU64Data::new(
+            &PedersenCommitment::from_bytes(&salary).unwrap(),
+            5_000,
+            &opening,
+        )
+        .unwrap();
+
+        let key = Pubkey::new_unique();
+        let owner = zk_token_proof_program::id();
+        let mut lamports = 0;
+        let mut data = ProofContextState::encode(
+            &Pubkey::new_unique(),
+            ProofType::RangeProofU64,
+            proof.context_data(),
+        );
+        let context = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+        assert!(verify_range_proof(&context, &salary).is_ok());
+        assert_eq!(
+            verify_range_proof(&context, &other).unwrap_err(),
+            GhostPayrollError::RangeProofMismatch.into()
+        );
+    }
+
+    /// A confidential salary transfer must encrypt the committed salary
+    #[test]
+    fn transfer_amount_must_match_commitment() {
+        let salary_opening = PedersenOpening::new_rand();
+        let salary = pedersen_commit(5_000_000_000, salary_opening.as_bytes()).unwrap();
+        let destination = ElGamalKeypair::new_rand();
+        let auditor = ElGamalKeypair::new_rand();
+
+        // The amount is encrypted as its low 16 bits and the rest, as a transfer does
+        let amount: u64 = 5_000_000_000;
+        let (amount_lo, amount_hi) = (amount & 0xffff, amount >> 16);
+        let (opening_lo, opening_hi) = (PedersenOpening::new_rand(), PedersenOpening::new_rand());
+        let pubkeys = [destination.pubkey(), auditor.pubkey()];
+        let proof = BatchedGroupedCiphertext2HandlesValidityProofData::new(
+            destination.pubkey(),
+            auditor.pubkey(),
+            &GroupedElGamal::encrypt_with(pubkeys, amount_lo, &opening_lo),
+            &GroupedElGamal::encrypt_with(pubkeys, amount_hi, &opening_hi),
+            amount_lo,
+            amount_hi,
+            &opening_lo,
+            &opening_hi,
+        )
+        .unwrap();
+
+        let mut opening_hi_shifted = opening_hi;
+        for _ in 0..CONFIDENTIAL_TRANSFER_LO_BITS {
+            opening_hi_shifted = &opening_hi_shifted + &opening_hi_shifted;
+        }
+        let opening_difference = &(&opening_lo + &opening_hi_shifted) - &salary_opening;
+
+        let key = Pubkey::new_unique();
+        let owner = zk_token_proof_program::id();
+        let mut lamports = 0;
+        let mut data = ProofContextState::encode(
+            &Pubkey::new_unique(),
+            ProofType::BatchedGroupedCiphertext2HandlesValidity,
+            proof.context_data(),
+        );
+        let context = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+
+        assert!(verify_transfer_amount(&context, &salary, opening_difference.as_bytes()).is_ok());
+
+        // Any other salary, or a guessed opening, is refused
+        let raise = pedersen_commit(5_500_000_000, salary_opening.as_bytes()).unwrap();
+        assert_eq!(
+            verify_transfer_amount(&context, &raise, opening_difference.as_bytes()).unwrap_err(),
+            GhostPayrollError::SalaryCommitmentMismatch.into()
+        );
+        assert!(verify_transfer_amount(&context, &salary, salary_opening.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,349 @@
+//! CPIs into the Token-2022 confidential transfer extension.
+//!
+//! A confidential treasury keeps its payroll balance as an ElGamal ciphertext
+//! under a key held by the company authority. Payouts move an encrypted
+//! amount to the employee's confidential token account; the transfer's split
+//! proofs (balance equality, amount ciphertext validity and range) are
+//! pre-verified into ZK Token proof context accounts and passed through, so
+//! the amount never appears in an instruction or in account data.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{
+        confidential_transfer::{
+            ciphertext_extraction::SourceDecryptHandles,
+            instruction::{
+                deposit, inner_apply_pending_balance, inner_empty_account, inner_withdraw,
+                transfer_with_split_proofs, ConfidentialTransferInstruction,
+                TransferSplitContextStateAccounts,
+            },
+            ConfidentialTransferAccount,
+        },
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    instruction::{reallocate, TokenInstruction},
+    proof::ProofLocation,
+    solana_zk_token_sdk::zk_token_elgamal::pod::{AeCiphertext, DecryptHandle},
+    state::Account as TokenState,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::state::*;
+
+/// Accounts for a confidential CPI on a company treasury
+pub struct ConfidentialTreasury<'a, 'info> {
+    pub company: &'a Account<'info, Company>,
+    pub treasury: &'a InterfaceAccount<'info, TokenAccount>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Context state accounts of the split proofs for one confidential transfer
+pub struct TransferProofs<'info> {
+    /// `VerifyCiphertextCommitmentEquality` over the treasury's new balance
+    pub equality: AccountInfo<'info>,
+
+    /// `VerifyBatchedGroupedCiphertext2HandlesValidity` over the encrypted amount
+    pub ciphertext_validity: AccountInfo<'info>,
+
+    /// `VerifyBatchedRangeProofU128` over the new balance and the amount
+    pub range: AccountInfo<'info>,
+}
+
+/// Whether `treasury` holds the confidential transfer extension
+pub fn is_confidential(treasury: &AccountInfo) -> Result<bool> {
+    let data = treasury.try_borrow_data()?;
+    let state = StateWithExtensions::<TokenState>::unpack(&data)?;
+    Ok(state.get_extension::<ConfidentialTransferAccount>().is_ok())
+}
+
+impl<'a, 'info> ConfidentialTreasury<'a, 'info> {
+    /// Invokes `instruction` on the token program, signing as the company PDA
+    fn invoke(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let company_seeds = &[
+            COMPANY_SEED,
+            self.company.authority.as_ref(),
+            &[self.company.bump],
+        ];
+        invoke_signed(instruction, account_infos, &[&company_seeds[..]]).map_err(Into::into)
+    }
+
+    /// Grows the treasury to hold the confidential transfer extension and
+    /// configures it with the ElGamal key proven in `pubkey_validity_proof`
+    pub fn configure(
+        &self,
+        payer: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+        pubkey_validity_proof: AccountInfo<'info>,
+        decryptable_zero_balance: [u8; 36],
+        maximum_pending_balance_credit_counter: u64,
+    ) -> Result<()> {
+        let token_program_id = *self.token_program.key;
+        let treasury = self.treasury.key();
+        let company = self.company.key();
+
+        let realloc_ix = reallocate(
+            &token_program_id,
+            &treasury,
+            payer.key,
+            &company,
+            &[],
+            &[ExtensionType::ConfidentialTransferAccount],
+        )?;
+        self.invoke(
+            &realloc_ix,
+            &[
+                self.treasury.to_account_info(),
+                payer,
+                system_program,
+                self.company.to_account_info(),
+            ],
+        )?;
+
+        let configure_ix = configure_account_instruction(
+            &token_program_id,
+            &treasury,
+            &self.mint.key(),
+            pubkey_validity_proof.key,
+            &company,
+            decryptable_zero_balance,
+            maximum_pending_balance_credit_counter,
+        );
+        self.invoke(
+            &configure_ix,
+            &[
+                self.treasury.to_account_info(),
+                self.mint.to_account_info(),
+                pubkey_validity_proof,
+                self.company.to_account_info(),
+            ],
+        )
+    }
+
+    /// Moves `amount` of the treasury's public balance into its encrypted
+    /// available balance. Funding stays public; only payouts are hidden.
+    pub fn shield(
+        &self,
+        amount: u64,
+        expected_pending_balance_credit_counter: u64,
+        new_decryptable_available_balance: [u8; 36],
+    ) -> Result<()> {
+        let token_program_id = *self.token_program.key;
+        let treasury = self.treasury.key();
+        let company = self.company.key();
+
+        let deposit_ix = deposit(
+            &token_program_id,
+            &treasury,
+            &self.mint.key(),
+            amount,
+            self.mint.decimals,
+            &company,
+            &[],
+        )?;
+        self.invoke(
+            &deposit_ix,
+            &[
+                self.treasury.to_account_info(),
+                self.mint.to_account_info(),
+                self.company.to_account_info(),
+            ],
+        )?;
+
+        let apply_ix = inner_apply_pending_balance(
+            &token_program_id,
+            &treasury,
+            expected_pending_balance_credit_counter,
+            AeCiphertext(new_decryptable_available_balance),
+            &company,
+            &[],
+        )?;
+        self.invoke(
+            &apply_ix,
+            &[
+                self.treasury.to_account_info(),
+                self.company.to_account_info(),
+            ],
+        )
+    }
+
+    /// Moves `amount` of the treasury's encrypted available balance back into
+    /// its public balance; `withdraw_proof` shows the balance covers it
+    pub fn unshield(
+        &self,
+        amount: u64,
+        new_decryptable_available_balance: [u8; 36],
+        withdraw_proof: AccountInfo<'info>,
+    ) -> Result<()> {
+        let withdraw_ix = inner_withdraw(
+            self.token_program.key,
+            &self.treasury.key(),
+            &self.mint.key(),
+            amount,
+            self.mint.decimals,
+            AeCiphertext(new_decryptable_available_balance),
+            &self.company.key(),
+            &[],
+            ProofLocation::ContextStateAccount(withdraw_proof.key),
+        )?;
+        self.invoke(
+            &withdraw_ix,
+            &[
+                self.treasury.to_account_info(),
+                self.mint.to_account_info(),
+                withdraw_proof,
+                self.company.to_account_info(),
+            ],
+        )
+    }
+
+    /// Zeroes the treasury's encrypted balances so the account can be closed;
+    /// `zero_balance_proof` shows the available balance encrypts zero
+    pub fn empty(&self, zero_balance_proof: AccountInfo<'info>) -> Result<()> {
+        let empty_ix = inner_empty_account(
+            self.token_program.key,
+            &self.treasury.key(),
+            &self.company.key(),
+            &[],
+            ProofLocation::ContextStateAccount(zero_balance_proof.key),
+        )?;
+        self.invoke(
+            &empty_ix,
+            &[
+                self.treasury.to_account_info(),
+                zero_balance_proof,
+                self.company.to_account_info(),
+            ],
+        )
+    }
+
+    /// Transfers the amount encrypted in `proofs` to `destination`. The token
+    /// program checks the proofs against each other, both accounts' ElGamal
+    /// keys and the treasury's encrypted balance; `source_decrypt_handles`
+    /// are the treasury's handles for the amount's low and high ciphertexts.
+    pub fn transfer(
+        &self,
+        destination: AccountInfo<'info>,
+        proofs: TransferProofs<'info>,
+        new_source_decryptable_available_balance: [u8; 36],
+        source_decrypt_handles: [u8; 64],
+    ) -> Result<()> {
+        let company = self.company.key();
+        let mut handle_lo = [0u8; 32];
+        handle_lo.copy_from_slice(&source_decrypt_handles[..32]);
+        let mut handle_hi = [0u8; 32];
+        handle_hi.copy_from_slice(&source_decrypt_handles[32..]);
+
+        let transfer_ix = transfer_with_split_proofs(
+            self.token_program.key,
+            &self.treasury.key(),
+            &self.mint.key(),
+            destination.key,
+            AeCiphertext(new_source_decryptable_available_balance),
+            &company,
+            TransferSplitContextStateAccounts {
+                equality_proof: proofs.equality.key,
+                ciphertext_validity_proof: proofs.ciphertext_validity.key,
+                range_proof: proofs.range.key,
+                authority: &company,
+                no_op_on_uninitialized_split_context_state: false,
+                close_split_context_state_accounts: None,
+            },
+            &SourceDecryptHandles {
+                lo: DecryptHandle(handle_lo),
+                hi: DecryptHandle(handle_hi),
+            },
+        )?;
+        self.invoke(
+            &transfer_ix,
+            &[
+                self.treasury.to_account_info(),
+                self.mint.to_account_info(),
+                destination,
+                proofs.equality,
+                proofs.ciphertext_validity,
+                proofs.range,
+                self.company.to_account_info(),
+            ],
+        )
+    }
+}
+
+/// `ConfigureAccount` with its proof in a context state account. The sdk's
+/// builder takes a host-only ciphertext type, so the instruction is encoded
+/// here: pod data ending in a zero proof instruction offset.
+fn configure_account_instruction(
+    token_program_id: &Pubkey,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+    pubkey_validity_proof: &Pubkey,
+    authority: &Pubkey,
+    decryptable_zero_balance: [u8; 36],
+    maximum_pending_balance_credit_counter: u64,
+) -> Instruction {
+    let mut data = TokenInstruction::ConfidentialTransferExtension.pack();
+    data.push(ConfidentialTransferInstruction::ConfigureAccount.into());
+    data.extend_from_slice(&decryptable_zero_balance);
+    data.extend_from_slice(&maximum_pending_balance_credit_counter.to_le_bytes());
+    data.push(0);
+
+    Instruction {
+        program_id: *token_program_id,
+        accounts: vec![
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*pubkey_validity_proof, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token_2022::spl_token_2022::{
+        extension::confidential_transfer::instruction::inner_configure_account,
+        solana_zk_token_sdk::encryption::auth_encryption::AeKey,
+    };
+
+    /// The hand-encoded instruction must match the sdk's host-side builder
+    #[test]
+    fn configure_account_matches_sdk_builder() {
+        let [token_account, mint, proof, authority] = [(); 4].map(|_| Pubkey::new_unique());
+        let zero_balance = AeKey::new_rand().encrypt(0);
+        let zero_balance_bytes = zero_balance.to_bytes();
+
+        let expected = inner_configure_account(
+            &anchor_spl::token_2022::ID,
+            &token_account,
+            &mint,
+            zero_balance,
+            65_536,
+            &authority,
+            &[],
+            ProofLocation::ContextStateAccount(&proof),
+        )
+        .unwrap();
+        let actual = configure_account_instruction(
+            &anchor_spl::token_2022::ID,
+            &token_account,
+            &mint,
+            &proof,
+            &authority,
+            zero_balance_bytes,
+            65_536,
+        );
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -3,10 +3,27 @@ pub const COMPANY_SEED: &[u8] = b"company";
 pub const EMPLOYEE_SEED: &[u8] = b"employee";
 pub const PAYMENT_PROOF_SEED: &[u8] = b"payment_proof";
 pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const TREASURY_LEDGER_SEED: &[u8] = b"treasury_ledger";
+pub const WITHDRAWAL_SEED: &[u8] = b"withdrawal";
+pub const PROTOCOL_CONFIG_SEED: &[u8] = b"protocol_config";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const PAYMENT_CORRECTION_SEED: &[u8] = b"payment_correction";
+pub const PAYROLL_RUN_SEED: &[u8] = b"payroll_run";
+pub const PAYROLL_PLAN_SEED: &[u8] = b"payroll_plan";
+pub const ADVANCE_SEED: &[u8] = b"advance";
+pub const WITHHOLDING_VAULT_SEED: &[u8] = b"withholding_vault";
+pub const VERIFYING_KEY_SEED: &[u8] = b"verifying_key";
 
 /// Business logic constants
 pub const MAX_EMPLOYEES_PER_COMPANY: u16 = 1000;
 pub const MAX_COMPANY_NAME_LENGTH: usize = 50;
+pub const MAX_TREASURY_MINTS: u8 = 8;
+
+/// Default runway (in pay periods) below which a low-runway event is emitted
+pub const DEFAULT_LOW_RUNWAY_THRESHOLD: u16 = 2;
+
+/// Rolling window (in days) the treasury spending cap applies to
+pub const SPENDING_CAP_WINDOW_DAYS: usize = 30;
 
 /// Minimum amounts (in smallest unit)
 pub const MIN_SALARY_AMOUNT: u64 = 1_000_000; // 1 USDC (6 decimals)
@@ -17,14 +34,68 @@ pub const USDC_DECIMALS: u8 = 6;
 /// Payment processing fees (basis points)
 pub const PAYMENT_PROCESSING_FEE_BPS: u16 = 30; // 0.3% fee
 pub const SHADOWWIRE_RELAYER_FEE_BPS: u16 = 100; // 1% (from ShadowWire docs)
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 500; // 5% ceiling for admin updates
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_KEEPER_TIP_BPS: u64 = 100; // keeper tip ceiling: 1% of the planned payment
+
+/// Withholding lines (tax, benefits) an employee's pay can be split into
+pub const MAX_WITHHOLDING_LINES: usize = 4;
+pub const MAX_WITHHOLDING_NAME_LENGTH: usize = 32;
 
 /// Max batch size for payroll processing
 pub const MAX_BATCH_SIZE: usize = 10;
 
+/// Size of the paid-employee bitmap on a payroll run; also caps the number
+/// of employee indices a company can ever assign
+pub const PAYROLL_RUN_BITMAP_BYTES: usize = 512;
+pub const MAX_EMPLOYEE_INDEX: u16 = (PAYROLL_RUN_BITMAP_BYTES * 8) as u16;
+
 /// Time constants (seconds)
 pub const SECONDS_PER_WEEK: i64 = 604_800;
 pub const SECONDS_PER_BIWEEK: i64 = 1_209_600;
-pub const SECONDS_PER_MONTH: i64 = 2_592_000; // ~30 days
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+/// How far in the future a pay calendar anchor may be set (seconds)
+pub const MAX_PAY_ANCHOR_LEAD: i64 = 366 * SECONDS_PER_DAY;
+
+/// Treasury withdrawal timelock bounds (seconds)
+pub const DEFAULT_WITHDRAWAL_DELAY: i64 = 3 * SECONDS_PER_DAY;
+pub const MIN_WITHDRAWAL_DELAY: i64 = SECONDS_PER_DAY;
+pub const MAX_WITHDRAWAL_DELAY: i64 = 30 * SECONDS_PER_DAY;
+
+/// Domain tag for payment amount commitments
+pub const AMOUNT_COMMITMENT_DOMAIN: &[u8] = b"ghost-payroll/amount-commitment/v1";
+
+/// Pedersen generator for committed amounts (the Ristretto basepoint)
+pub const PEDERSEN_G: [u8; 32] = [
+    0xe2, 0xf2, 0xae, 0x0a, 0x6a, 0xbc, 0x4e, 0x71, 0xa8, 0x84, 0xa9, 0x61, 0xc5, 0x00, 0x51, 0x5f,
+    0x58, 0xe3, 0x0b, 0x6a, 0xa5, 0x82, 0xdd, 0x8d, 0xb6, 0xa6, 0x59, 0x45, 0xe0, 0x8d, 0x2d, 0x76,
+];
+
+/// Pedersen generator for openings (SHA3-512 hash-to-point of `PEDERSEN_G`)
+pub const PEDERSEN_H: [u8; 32] = [
+    0x8c, 0x92, 0x40, 0xb4, 0x56, 0xa9, 0xe6, 0xdc, 0x65, 0xc3, 0x77, 0xa1, 0x04, 0x8d, 0x74, 0x5f,
+    0x94, 0xa0, 0x8c, 0xdb, 0x7f, 0x44, 0xcb, 0xcd, 0x7b, 0x46, 0xf3, 0x40, 0x48, 0x87, 0x11, 0x34,
+];
+
+/// Confidential transfers encrypt an amount's low bits and the rest separately
+pub const CONFIDENTIAL_TRANSFER_LO_BITS: u32 = 16;
+
+/// Groth16 payment proofs: `a || b || c` and the payment circuit's public input count
+pub const GROTH16_PROOF_SIZE: usize = 256;
+pub const PAYMENT_PROOF_PUBLIC_INPUTS: usize = 5;
+
+/// BN254 base field modulus (big-endian)
+pub const BN254_BASE_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// BN254 group order, the bound on public inputs (big-endian)
+pub const BN254_SCALAR_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
 
 /// Encryption sizes
 pub const ENCRYPTED_SALARY_MAX_SIZE: usize = 256;
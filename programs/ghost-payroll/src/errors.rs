@@ -64,4 +64,145 @@ pub enum GhostPayrollError {
 
     #[msg("Invalid encrypted salary data")]
     InvalidEncryptedSalary,
+
+    #[msg("Deposit amount must be greater than zero")]
+    InvalidDepositAmount,
+
+    #[msg("Withdrawal amount must be greater than zero")]
+    InvalidWithdrawalAmount,
+
+    #[msg("Withdrawal delay is outside the allowed range")]
+    InvalidWithdrawalDelay,
+
+    #[msg("Withdrawal timelock has not yet elapsed")]
+    WithdrawalNotReady,
+
+    #[msg("Withdrawal is not pending")]
+    WithdrawalNotPending,
+
+    #[msg("Maximum number of treasury mints reached")]
+    MaxTreasuryMintsReached,
+
+    #[msg("Treasury spending cap for the rolling window exceeded")]
+    SpendingCapExceeded,
+
+    #[msg("Protocol fee exceeds the allowed maximum")]
+    InvalidProtocolFee,
+
+    #[msg("Company still has active employees")]
+    ActiveEmployeesRemain,
+
+    #[msg("Final wages are still owed to removed employees")]
+    FinalPaymentsPending,
+
+    #[msg("Company still has open treasuries")]
+    TreasuriesRemain,
+
+    #[msg("Program is paused by the guardian")]
+    ProtocolPaused,
+
+    #[msg("Company is paused")]
+    CompanyPaused,
+
+    #[msg("Already paused")]
+    AlreadyPaused,
+
+    #[msg("Not paused")]
+    NotPaused,
+
+    #[msg("Correction amount must be greater than zero and at most the amount paid")]
+    InvalidCorrectionAmount,
+
+    #[msg("Payment correction is not pending")]
+    CorrectionNotPending,
+
+    #[msg("Payroll batch is empty, too large, or does not match its accounts")]
+    InvalidBatchSize,
+
+    #[msg("Payroll run is not open")]
+    PayrollRunNotOpen,
+
+    #[msg("Company already has an open payroll run")]
+    PayrollRunAlreadyOpen,
+
+    #[msg("Payroll period does not follow the last run")]
+    InvalidPayrollPeriod,
+
+    #[msg("Payroll plan amount, period count or keeper tip is invalid")]
+    InvalidPayrollPlan,
+
+    #[msg("All approved periods of the payroll plan have been paid")]
+    PayrollPlanExhausted,
+
+    #[msg("No streamed wages have accrued")]
+    NothingAccrued,
+
+    #[msg("Pay calendar anchor is outside the allowed range")]
+    InvalidPayAnchor,
+
+    #[msg("Payment does not match the prorated amount for the pay period")]
+    InvalidProratedAmount,
+
+    #[msg("Invalid advance amount or repayment schedule")]
+    InvalidAdvance,
+
+    #[msg("Employee is repaying an advance; its accounts must be supplied")]
+    AdvanceAccountRequired,
+
+    #[msg("Invalid withholding line")]
+    InvalidWithholdingLine,
+
+    #[msg("Withholdings exceed the gross payment")]
+    WithholdingExceedsPayment,
+
+    #[msg("Withholding vaults do not match the employee's withholding lines")]
+    WithholdingVaultMismatch,
+
+    #[msg("Amount does not open the supplied amount commitment")]
+    InvalidAmountCommitment,
+
+    #[msg("Salary payment does not match the employee's salary commitment")]
+    SalaryCommitmentMismatch,
+
+    #[msg("Invalid Pedersen commitment or opening")]
+    InvalidPedersenCommitment,
+
+    #[msg("Account is not a verified 64-bit range proof context")]
+    InvalidRangeProof,
+
+    #[msg("Range proof does not cover the expected commitment")]
+    RangeProofMismatch,
+
+    #[msg("Invalid Groth16 verifying key")]
+    InvalidVerifyingKey,
+
+    #[msg("Account is not a verified confidential transfer proof context")]
+    InvalidTransferProof,
+
+    #[msg("Withholdings, advance repayments and prorated salary need a plaintext payout")]
+    ConfidentialPayoutUnsupported,
+
+    #[msg("On-cycle payments must go through the company's open payroll run")]
+    PayrollRunRequired,
+
+    #[msg("Streamed wages are still awaiting withdrawal by removed employees")]
+    StreamWithdrawalsPending,
+
+    #[msg("Employee is paid by stream, not on-cycle salary")]
+    EmployeeStreamed,
+
+    #[msg("Payment proofs must cover the employee's latest payment and its amount commitment")]
+    PaymentNotRecorded,
+
+    #[msg("Withholding vaults must be remitted and closed first")]
+    WithholdingVaultsRemain,
+
+    #[msg("Queued withdrawals must be executed or cancelled first")]
+    WithdrawalsPending,
+
+    #[msg("Withholding vault still holds unremitted funds")]
+    WithholdingVaultNotEmpty,
+
+    #[msg("Confidential treasury balance must be unshielded and proven zero first")]
+    ConfidentialBalanceRemains,
 }
@@ -0,0 +1,503 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{PaymentCategory, PaymentFrequency, WithholdingAmount, WithholdingLine};
+
+// ==================== TREASURY EVENTS ====================
+#[event]
+pub struct TreasuryMintAdded {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub treasury_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryFunded {
+    pub company: Pubkey,
+    pub funder: Pubkey,
+    pub mint: Pubkey,
+    /// Tokens sent by the funder (before any transfer fee)
+    pub amount: u64,
+    /// Transfer fee withheld by the mint, not credited to the treasury
+    pub fee_withheld: u64,
+    pub total_deposited: u64,
+    pub deposit_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryClosed {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub amount_returned: u64,
+    pub destination: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalQueued {
+    pub company: Pubkey,
+    pub withdrawal_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalExecuted {
+    pub company: Pubkey,
+    pub withdrawal_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Transfer fee withheld by the mint, not credited to the destination
+    pub fee_withheld: u64,
+    pub destination: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalCancelled {
+    pub company: Pubkey,
+    pub withdrawal_id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalDelayUpdated {
+    pub company: Pubkey,
+    pub old_delay: i64,
+    pub new_delay: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayrollObligationUpdated {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub period_obligation: u64,
+    pub low_runway_threshold: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LowRunway {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub treasury_balance: u64,
+    pub period_obligation: u64,
+    pub runway_periods: u64,
+    pub low_runway_threshold: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SpendingCapUpdated {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub spending_cap: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SpendingCapIncreaseQueued {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub current_cap: u64,
+    pub pending_cap: u64,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfidentialTreasuryConfigured {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryShielded {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub protocol_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryUnshielded {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// ==================== PAYMENT EVENTS ====================
+#[event]
+pub struct PayScheduleUpdated {
+    pub company: Pubkey,
+    pub employee: Option<Pubkey>,
+    pub payment_frequency: PaymentFrequency,
+    pub pay_anchor: i64,
+    pub next_payment_due: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OffCyclePaymentProcessed {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub mint: Pubkey,
+    pub category: PaymentCategory,
+    pub amount: u64,
+    pub next_payment_due: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PaymentProrated {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub amount: u64,
+    pub full_period_amount: u64,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub worked_seconds: i64,
+    pub final_payment: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PaymentCorrectionOpened {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub payment_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PaymentCorrectionCompleted {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub payment_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_clawed_back: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PaymentCorrectionCancelled {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub payment_id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfidentialPaymentProcessed {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub mint: Pubkey,
+    pub category: PaymentCategory,
+    pub payment_number: u64,
+    pub next_payment_due: i64,
+    pub final_payment: bool,
+    pub timestamp: i64,
+}
+
+// ==================== STREAMING EVENTS ====================
+#[event]
+pub struct StreamRateUpdated {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub old_rate: u64,
+    pub new_rate: u64,
+    pub accrued: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StreamPauseUpdated {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub paused: bool,
+    pub accrued: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccruedWithdrawn {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_streamed: u64,
+    pub timestamp: i64,
+}
+
+// ==================== WITHHOLDING EVENTS ====================
+
+#[event]
+pub struct WithholdingVaultCreated {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub name: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithholdingVaultClosed {
+    pub company: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub name: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithholdingLinesUpdated {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub lines: Vec<WithholdingLine>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PaymentWithheld {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub category: PaymentCategory,
+    pub gross_amount: u64,
+    pub net_amount: u64,
+    pub withholdings: Vec<WithholdingAmount>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithholdingRemittanceQueued {
+    pub company: Pubkey,
+    pub withdrawal_id: u64,
+    pub vault: Pubkey,
+    pub name: String,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+// ==================== ADVANCE EVENTS ====================
+
+#[event]
+pub struct AdvanceIssued {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub mint: Pubkey,
+    pub principal: u64,
+    pub repayment_per_period: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdvanceRepaymentWithheld {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub repayment: u64,
+    pub outstanding: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdvanceRepaid {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub principal: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdvanceWrittenOff {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub mint: Pubkey,
+    pub principal: u64,
+    pub unrecovered: u64,
+    pub timestamp: i64,
+}
+
+// ==================== PAYROLL RUN EVENTS ====================
+#[event]
+pub struct PayrollRunOpened {
+    pub company: Pubkey,
+    pub period: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayrollRunResumed {
+    pub company: Pubkey,
+    pub period: u64,
+    pub payments_made: u16,
+    pub resume_count: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayrollRunFinalized {
+    pub company: Pubkey,
+    pub period: u64,
+    pub payments_made: u16,
+    pub total_paid: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayrollPlanApproved {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub mint: Pubkey,
+    pub amount_commitment: [u8; 32],
+    pub periods_approved: u16,
+    pub keeper_tip: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayrollPlanCranked {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub keeper: Pubkey,
+    pub keeper_tip: u64,
+    pub periods_paid: u16,
+    pub periods_approved: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayrollPlanRevoked {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub periods_paid: u16,
+    pub periods_approved: u16,
+    pub timestamp: i64,
+}
+
+// ==================== COMPANY EVENTS ====================
+#[event]
+pub struct CompanyClosed {
+    pub company: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompanyPaused {
+    pub company: Pubkey,
+    pub authority: Pubkey,
+    pub reason_code: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompanyUnpaused {
+    pub company: Pubkey,
+    pub authority: Pubkey,
+    pub reason_code: u16,
+    pub paused_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BudgetCommitmentUpdated {
+    pub company: Pubkey,
+    pub budget_commitment: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BudgetComplianceVerified {
+    pub company: Pubkey,
+    pub budget_commitment: [u8; 32],
+    pub salary_commitment_total: [u8; 32],
+    pub active_employees: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifyingKeyRegistered {
+    pub company: Pubkey,
+    pub verifying_key: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifyingKeyUpdated {
+    pub company: Pubkey,
+    pub verifying_key: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifyingKeyClosed {
+    pub company: Pubkey,
+    pub verifying_key: Pubkey,
+    pub timestamp: i64,
+}
+
+// ==================== PROTOCOL EVENTS ====================
+#[event]
+pub struct ProtocolFeeUpdated {
+    pub admin: Pubkey,
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolFeeCollected {
+    pub company: Pubkey,
+    pub employee: Pubkey,
+    pub mint: Pubkey,
+    pub payment_amount: u64,
+    pub fee_amount: u64,
+    pub fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolFeesSwept {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub admin: Pubkey,
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolPaused {
+    pub guardian: Pubkey,
+    pub reason_code: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolUnpaused {
+    pub guardian: Pubkey,
+    pub reason_code: u16,
+    pub paused_at: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,274 @@
+//! Groth16 proof verification over BN254 using the alt_bn128 syscalls.
+//!
+//! Points use the big-endian EIP-196/197 encoding the syscalls expect: G1 is
+//! `x || y` and G2 is `x_im || x_re || y_im || y_re`, each coordinate 32
+//! bytes. A proof is `a (G1) || b (G2) || c (G1)`, and public inputs are
+//! big-endian scalars below the BN254 group order.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::alt_bn128::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+/// A Groth16 proof
+pub struct Groth16Proof {
+    pub a: [u8; 64],
+    pub b: [u8; 128],
+    pub c: [u8; 64],
+}
+
+impl Groth16Proof {
+    /// Parses a proof from its `a || b || c` encoding
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        require!(
+            bytes.len() == GROTH16_PROOF_SIZE,
+            GhostPayrollError::InvalidPaymentProof
+        );
+        let mut proof = Self {
+            a: [0; 64],
+            b: [0; 128],
+            c: [0; 64],
+        };
+        proof.a.copy_from_slice(&bytes[..64]);
+        proof.b.copy_from_slice(&bytes[64..192]);
+        proof.c.copy_from_slice(&bytes[192..]);
+        Ok(proof)
+    }
+}
+
+/// Public inputs of the payment circuit: the amount commitment and employee
+/// wallet (each split into 128-bit halves to fit the scalar field) and the
+/// payment ID. A valid proof attests the committed amount was paid to that
+/// employee under that payment ID.
+pub fn payment_public_inputs(
+    amount_commitment: &[u8; 32],
+    employee: &Pubkey,
+    payment_id: u64,
+) -> [[u8; 32]; PAYMENT_PROOF_PUBLIC_INPUTS] {
+    let mut inputs = [[0u8; 32]; PAYMENT_PROOF_PUBLIC_INPUTS];
+    for (input, half) in inputs.iter_mut().zip(
+        amount_commitment
+            .chunks(16)
+            .chain(employee.as_ref().chunks(16)),
+    ) {
+        input[16..].copy_from_slice(half);
+    }
+    inputs[4][24..].copy_from_slice(&payment_id.to_be_bytes());
+    inputs
+}
+
+/// Fails unless `point` is an encoded G1 point
+pub fn validate_g1(point: &[u8; 64]) -> Result<()> {
+    alt_bn128_addition(&[&point[..], &[0u8; 64][..]].concat())
+        .map_err(|_| GhostPayrollError::InvalidVerifyingKey)?;
+    Ok(())
+}
+
+/// Fails unless `point` is an encoded G2 point
+pub fn validate_g2(point: &[u8; 128]) -> Result<()> {
+    alt_bn128_pairing(&[&[0u8; 64][..], &point[..]].concat())
+        .map_err(|_| GhostPayrollError::InvalidVerifyingKey)?;
+    Ok(())
+}
+
+/// Fails unless every point of a verifying key is on the curve
+pub fn validate_verifying_key(
+    alpha_g1: &[u8; 64],
+    beta_g2: &[u8; 128],
+    gamma_g2: &[u8; 128],
+    delta_g2: &[u8; 128],
+    ic: &[[u8; 64]; PAYMENT_PROOF_PUBLIC_INPUTS + 1],
+) -> Result<()> {
+    validate_g1(alpha_g1)?;
+    for point in [beta_g2, gamma_g2, delta_g2] {
+        validate_g2(point)?;
+    }
+    for point in ic.iter() {
+        validate_g1(point)?;
+    }
+    Ok(())
+}
+
+/// Negates a G1 point by replacing `y` with `q - y`
+fn negate_g1(point: &[u8; 64]) -> [u8; 64] {
+    let mut negated = *point;
+    if point[32..].iter().all(|&byte| byte == 0) {
+        return negated;
+    }
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let mut digit = BN254_BASE_FIELD_MODULUS[i] as i16 - point[32 + i] as i16 - borrow;
+        borrow = (digit < 0) as i16;
+        if digit < 0 {
+            digit += 256;
+        }
+        negated[32 + i] = digit as u8;
+    }
+    negated
+}
+
+/// Fails with `ProofVerificationFailed` unless `proof` verifies against `vk`
+/// for `public_inputs`
+pub fn verify_groth16(
+    vk: &PaymentVerifyingKey,
+    proof: &Groth16Proof,
+    public_inputs: &[[u8; 32]; PAYMENT_PROOF_PUBLIC_INPUTS],
+) -> Result<()> {
+    let failed = |_| error!(GhostPayrollError::ProofVerificationFailed);
+
+    // vk_x = ic[0] + sum(input_i * ic[i + 1])
+    let mut vk_x = vk.ic[0].to_vec();
+    for (input, ic) in public_inputs.iter().zip(vk.ic[1..].iter()) {
+        require!(
+            *input < BN254_SCALAR_MODULUS,
+            GhostPayrollError::ProofVerificationFailed
+        );
+        let term = alt_bn128_multiplication(&[&ic[..], &input[..]].concat()).map_err(failed)?;
+        vk_x = alt_bn128_addition(&[&vk_x[..], &term[..]].concat()).map_err(failed)?;
+    }
+
+    // e(-a, b) * e(alpha, beta) * e(vk_x, gamma) * e(c, delta) == 1
+    let pairing_input = [
+        &negate_g1(&proof.a)[..],
+        &proof.b[..],
+        &vk.alpha_g1[..],
+        &vk.beta_g2[..],
+        &vk_x[..],
+        &vk.gamma_g2[..],
+        &proof.c[..],
+        &vk.delta_g2[..],
+    ]
+    .concat();
+    let result = alt_bn128_pairing(&pairing_input).map_err(failed)?;
+    require!(
+        result.last() == Some(&1) && result[..result.len() - 1].iter().all(|&byte| byte == 0),
+        GhostPayrollError::ProofVerificationFailed
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const G2_GENERATOR: [u8; 128] = [
+        0x19, 0x8e, 0x93, 0x93, 0x92, 0x0d, 0x48, 0x3a, 0x72, 0x60, 0xbf, 0xb7, 0x31, 0xfb, 0x5d,
+        0x25, 0xf1, 0xaa, 0x49, 0x33, 0x35, 0xa9, 0xe7, 0x12, 0x97, 0xe4, 0x85, 0xb7, 0xae, 0xf3,
+        0x12, 0xc2, 0x18, 0x00, 0xde, 0xef, 0x12, 0x1f, 0x1e, 0x76, 0x42, 0x6a, 0x00, 0x66, 0x5e,
+        0x5c, 0x44, 0x79, 0x67, 0x43, 0x22, 0xd4, 0xf7, 0x5e, 0xda, 0xdd, 0x46, 0xde, 0xbd, 0x5c,
+        0xd9, 0x92, 0xf6, 0xed, 0x09, 0x06, 0x89, 0xd0, 0x58, 0x5f, 0xf0, 0x75, 0xec, 0x9e, 0x99,
+        0xad, 0x69, 0x0c, 0x33, 0x95, 0xbc, 0x4b, 0x31, 0x33, 0x70, 0xb3, 0x8e, 0xf3, 0x55, 0xac,
+        0xda, 0xdc, 0xd1, 0x22, 0x97, 0x5b, 0x12, 0xc8, 0x5e, 0xa5, 0xdb, 0x8c, 0x6d, 0xeb, 0x4a,
+        0xab, 0x71, 0x80, 0x8d, 0xcb, 0x40, 0x8f, 0xe3, 0xd1, 0xe7, 0x69, 0x0c, 0x43, 0xd3, 0x7b,
+        0x4c, 0xe6, 0xcc, 0x01, 0x66, 0xfa, 0x7d, 0xaa,
+    ];
+
+    /// `k * G1` for the generator `(1, 2)`
+    fn g1(k: u64) -> [u8; 64] {
+        let mut input = [0u8; 96];
+        input[31] = 1;
+        input[63] = 2;
+        input[88..].copy_from_slice(&k.to_be_bytes());
+        alt_bn128_multiplication(&input)
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    fn g1_add(p: &[u8; 64], q: &[u8; 64]) -> [u8; 64] {
+        alt_bn128_addition(&[&p[..], &q[..]].concat())
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    /// Test key with every G2 element at the generator, so a proof with
+    /// `b = G2` verifies exactly when `a = alpha + vk_x + c`
+    fn test_key() -> PaymentVerifyingKey {
+        PaymentVerifyingKey {
+            company: Pubkey::default(),
+            alpha_g1: g1(7),
+            beta_g2: G2_GENERATOR,
+            gamma_g2: G2_GENERATOR,
+            delta_g2: G2_GENERATOR,
+            ic: [g1(11), g1(13), g1(17), g1(19), g1(23), g1(29)],
+            registered_at: 0,
+            bump: 0,
+        }
+    }
+
+    fn prove(
+        vk: &PaymentVerifyingKey,
+        inputs: &[[u8; 32]; PAYMENT_PROOF_PUBLIC_INPUTS],
+    ) -> Groth16Proof {
+        let mut vk_x = vk.ic[0];
+        for (input, ic) in inputs.iter().zip(vk.ic[1..].iter()) {
+            let term = alt_bn128_multiplication(&[&ic[..], &input[..]].concat()).unwrap();
+            vk_x = g1_add(&vk_x, &term.try_into().unwrap());
+        }
+        let c = g1(5);
+        Groth16Proof {
+            a: g1_add(&g1_add(&vk.alpha_g1, &vk_x), &c),
+            b: G2_GENERATOR,
+            c,
+        }
+    }
+
+    #[test]
+    fn verifies_proof_for_its_payment_only() {
+        let vk = test_key();
+        let employee = Pubkey::new_unique();
+        let inputs = payment_public_inputs(&[9u8; 32], &employee, 42);
+        let proof = prove(&vk, &inputs);
+
+        assert!(verify_groth16(&vk, &proof, &inputs).is_ok());
+        assert!(verify_groth16(
+            &vk,
+            &proof,
+            &payment_public_inputs(&[9u8; 32], &employee, 43)
+        )
+        .is_err());
+        assert!(verify_groth16(
+            &vk,
+            &proof,
+            &payment_public_inputs(&[8u8; 32], &employee, 42)
+        )
+        .is_err());
+
+        let mut forged = prove(&vk, &inputs);
+        forged.c = g1(6);
+        assert!(verify_groth16(&vk, &forged, &inputs).is_err());
+    }
+
+    #[test]
+    fn rejects_inputs_outside_the_scalar_field() {
+        let vk = test_key();
+        let mut inputs = payment_public_inputs(&[9u8; 32], &Pubkey::new_unique(), 42);
+        let proof = prove(&vk, &inputs);
+        inputs[4] = BN254_SCALAR_MODULUS;
+        assert!(verify_groth16(&vk, &proof, &inputs).is_err());
+    }
+
+    #[test]
+    fn negation_cancels_in_g1() {
+        let point = g1(3);
+        let sum = alt_bn128_addition(&[&point[..], &negate_g1(&point)[..]].concat()).unwrap();
+        assert_eq!(sum, vec![0u8; 64]);
+        assert_eq!(negate_g1(&[0u8; 64]), [0u8; 64]);
+    }
+
+    #[test]
+    fn validates_key_points() {
+        assert!(validate_g1(&g1(3)).is_ok());
+        assert!(validate_g2(&G2_GENERATOR).is_ok());
+
+        let mut off_curve = g1(3);
+        off_curve[63] ^= 1;
+        assert!(validate_g1(&off_curve).is_err());
+        let mut off_twist = G2_GENERATOR;
+        off_twist[127] ^= 1;
+        assert!(validate_g2(&off_twist).is_err());
+    }
+}
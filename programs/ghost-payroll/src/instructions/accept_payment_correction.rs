@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct AcceptPaymentCorrection<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.authority.as_ref()],
+        bump = company.bump,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee_wallet.key().as_ref()],
+        bump = employee.bump,
+        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound
+    )]
+    pub employee: Account<'info, Employee>,
+
+    /// Record of the payment being corrected
+    #[account(
+        mut,
+        address = payment_correction.payment_proof @ GhostPayrollError::InvalidPaymentProof,
+        constraint = payment_proof.company == company.key() @ GhostPayrollError::InvalidPaymentProof
+    )]
+    pub payment_proof: Account<'info, PaymentProof>,
+
+    #[account(
+        mut,
+        close = company_authority,
+        seeds = [PAYMENT_CORRECTION_SEED, payment_proof.key().as_ref()],
+        bump = payment_correction.bump,
+        constraint = payment_correction.company == company.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = payment_correction.employee == employee_wallet.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = payment_correction.status == CorrectionStatus::Pending @ GhostPayrollError::CorrectionNotPending
+    )]
+    pub payment_correction: Account<'info, PaymentCorrection>,
+
+    /// Company treasury token account the tokens are returned to
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, company.key().as_ref(), payment_correction.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Employee's token account the tokens are returned from
+    #[account(
+        mut,
+        constraint = employee_token_account.owner == employee_wallet.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = employee_token_account.mint == payment_correction.mint @ GhostPayrollError::InvalidTokenMint
+    )]
+    pub employee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint of the corrected payment
+    #[account(
+        address = payment_correction.mint @ GhostPayrollError::InvalidTokenMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Employee wallet (must sign to consent to the return)
+    pub employee_wallet: Signer<'info>,
+
+    /// Company authority, refunded the correction's rent
+    /// CHECK: Only receives lamports; address is the company's authority
+    #[account(
+        mut,
+        address = company.authority @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub company_authority: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub(crate) fn handler(ctx: Context<AcceptPaymentCorrection>) -> Result<()> {
+    let amount = ctx.accounts.payment_correction.amount;
+
+    // Return tokens from employee to treasury
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.employee_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+            authority: ctx.accounts.employee_wallet.to_account_info(),
+        },
+    );
+
+    token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    let employee = &mut ctx.accounts.employee;
+    let payment_proof = &mut ctx.accounts.payment_proof;
+    let payment_correction = &mut ctx.accounts.payment_correction;
+    let clock = Clock::get()?;
+
+    // Annotate the original payment record
+    payment_proof.status = PaymentStatus::Corrected;
+    payment_proof.clawed_back_amount = payment_proof
+        .clawed_back_amount
+        .checked_add(amount)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    // Annotate the employee's counters
+    employee.total_clawed_back = employee
+        .total_clawed_back
+        .checked_add(amount)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    employee.correction_count = employee
+        .correction_count
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    payment_correction.status = CorrectionStatus::Completed;
+    payment_correction.completed_at = clock.unix_timestamp;
+
+    emit!(PaymentCorrectionCompleted {
+        company: payment_correction.company,
+        employee: payment_correction.employee,
+        payment_id: payment_correction.payment_id,
+        mint: payment_correction.mint,
+        amount,
+        total_clawed_back: employee.total_clawed_back,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Payment correction completed");
+    msg!("Payment ID: {}", payment_correction.payment_id);
+    msg!("Returned to treasury: {}", amount);
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::zk_token_proof_program;
+use anchor_spl::token_interface::TokenAccount;
 
+use crate::commitment::*;
 use crate::constants::*;
 use crate::errors::*;
 use crate::state::*;
@@ -14,10 +16,19 @@ pub struct AddEmployee<'info> {
         bump = company.bump,
         constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
         constraint = company.is_active @ GhostPayrollError::CompanyInactive,
-        constraint = company.employee_count < MAX_EMPLOYEES_PER_COMPANY @ GhostPayrollError::MaxEmployeesReached
+        constraint = company.employee_count < MAX_EMPLOYEES_PER_COMPANY @ GhostPayrollError::MaxEmployeesReached,
+        constraint = company.next_employee_index < MAX_EMPLOYEE_INDEX @ GhostPayrollError::MaxEmployeesReached,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
     )]
     pub company: Account<'info, Company>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     #[account(
         init,
         payer = authority,
@@ -31,18 +42,29 @@ pub struct AddEmployee<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    /// Employee's token account for receiving payments
-    /// CHECK: We validate the owner and mint
+    /// Employee's token account for receiving payments; its mint becomes the payout mint
+    /// CHECK: We validate the owner, and the treasury ledger seeds validate the mint
+    #[account(
+        constraint = employee_token_account.owner == employee_wallet @ GhostPayrollError::InvalidTokenMint
+    )]
+    pub employee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Treasury ledger for the payout mint (proves the company holds a treasury for it)
     #[account(
-        constraint = employee_token_account.owner == employee_wallet @ GhostPayrollError::InvalidTokenMint,
-        constraint = employee_token_account.mint == company.payment_token @ GhostPayrollError::InvalidTokenMint
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), employee_token_account.mint.as_ref()],
+        bump = treasury_ledger.bump
     )]
-    pub employee_token_account: Account<'info, TokenAccount>,
+    pub treasury_ledger: Account<'info, TreasuryLedger>,
+
+    /// Context state of a `VerifyRangeProofU64` proof over the salary commitment
+    /// CHECK: Owner is the proof program, contents are validated in the handler
+    #[account(owner = zk_token_proof_program::id() @ GhostPayrollError::InvalidRangeProof)]
+    pub salary_range_proof: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<AddEmployee>,
     employee_wallet: Pubkey,
     encrypted_salary: Vec<u8>,
@@ -54,6 +76,9 @@ pub fn handler(
         !encrypted_salary.is_empty() && encrypted_salary.len() <= Employee::MAX_ENCRYPTED_SALARY_SIZE,
         GhostPayrollError::InvalidEncryptedSalary
     );
+    validate_pedersen_commitment(&salary_commitment)?;
+    // A commitment to a negative or wrapped salary would understate the budget total
+    verify_range_proof(&ctx.accounts.salary_range_proof, &salary_commitment)?;
 
     let company = &mut ctx.accounts.company;
     let employee = &mut ctx.accounts.employee;
@@ -63,23 +88,55 @@ pub fn handler(
     // Initialize employee account
     employee.wallet = employee_wallet;
     employee.company = company.key();
+    employee.employee_index = company.next_employee_index;
     employee.encrypted_salary = encrypted_salary;
     employee.salary_commitment = salary_commitment;
+    employee.payout_mint = ctx.accounts.employee_token_account.mint;
     employee.payment_frequency = payment_frequency;
+    employee.pay_anchor = company.pay_anchor;
     employee.join_date = current_time;
     employee.last_payment_date = current_time;
     employee.total_payments_received = 0;
+    employee.last_payment_commitment = [0; 32];
+    employee.last_payment_category = PaymentCategory::default();
+    employee.next_payment_due =
+        payment_frequency.next_payment_after(employee.pay_anchor, current_time);
+    employee.paid_through = current_time;
+    employee.off_cycle_payment_count = 0;
+    employee.confidential_payment_count = 0;
+    employee.payments_by_category = [0; PaymentCategory::COUNT];
+    employee.amount_by_category = [0; PaymentCategory::COUNT];
+    employee.last_proration = ProrationBasis::default();
+    employee.has_advance = false;
+    employee.withholding_lines = Vec::new();
     employee.is_active = true;
+    employee.termination_date = 0;
+    employee.final_payment_pending = false;
+    employee.total_clawed_back = 0;
+    employee.correction_count = 0;
+    employee.stream_rate = 0;
+    employee.stream_paused = false;
+    employee.stream_settled_at = current_time;
+    employee.stream_accrued = 0;
+    employee.total_streamed = 0;
     employee.bump = ctx.bumps.employee;
 
+    // Fold the salary into the company's committed payroll total
+    company.add_salary_commitment(&salary_commitment)?;
+
     // Increment company employee count
     company.employee_count = company
         .employee_count
         .checked_add(1)
         .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    company.next_employee_index = company
+        .next_employee_index
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
 
     msg!("Employee added to company: {}", company.name);
     msg!("Employee wallet: {}", employee.wallet);
+    msg!("Payout mint: {}", employee.payout_mint);
     msg!("Payment frequency: {:?}", employee.payment_frequency);
     msg!("Total employees: {}", company.employee_count);
 
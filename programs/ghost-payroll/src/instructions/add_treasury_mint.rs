@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct AddTreasuryMint<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = company.treasury_count < MAX_TREASURY_MINTS @ GhostPayrollError::MaxTreasuryMintsReached,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Token mint the company will pay in, owned by either token program
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Company treasury token account for the mint (owned by company PDA)
+    #[account(
+        init,
+        payer = authority,
+        seeds = [TREASURY_SEED, company.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = company,
+        token::token_program = token_program,
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Accounting record for the new treasury
+    #[account(
+        init,
+        payer = authority,
+        space = TreasuryLedger::LEN,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_ledger: Account<'info, TreasuryLedger>,
+
+    /// Company authority (must sign and pay)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn handler(ctx: Context<AddTreasuryMint>) -> Result<()> {
+    let company = &mut ctx.accounts.company;
+    let treasury_ledger = &mut ctx.accounts.treasury_ledger;
+    let clock = Clock::get()?;
+
+    // Initialize treasury ledger
+    treasury_ledger.company = company.key();
+    treasury_ledger.mint = ctx.accounts.mint.key();
+    treasury_ledger.treasury = ctx.accounts.treasury.key();
+    treasury_ledger.total_deposited = 0;
+    treasury_ledger.deposit_count = 0;
+    treasury_ledger.last_deposit_timestamp = 0;
+    treasury_ledger.period_obligation = 0;
+    treasury_ledger.low_runway_threshold = DEFAULT_LOW_RUNWAY_THRESHOLD;
+    treasury_ledger.spending_cap = 0;
+    treasury_ledger.pending_spending_cap = 0;
+    treasury_ledger.pending_cap_effective_at = 0;
+    treasury_ledger.outflow_day = 0;
+    treasury_ledger.outflow_buckets = [0; SPENDING_CAP_WINDOW_DAYS];
+    treasury_ledger.bump = ctx.bumps.treasury_ledger;
+
+    company.treasury_count = company
+        .treasury_count
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    emit!(TreasuryMintAdded {
+        company: company.key(),
+        mint: treasury_ledger.mint,
+        treasury: treasury_ledger.treasury,
+        treasury_count: company.treasury_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Treasury added for company: {}", company.name);
+    msg!("Mint: {}", treasury_ledger.mint);
+    msg!("Treasuries: {}", company.treasury_count);
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+use crate::commitment::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ApprovePayrollPlan<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = employee.bump,
+        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound,
+        constraint = employee.is_active @ GhostPayrollError::EmployeeInactive
+    )]
+    pub employee: Account<'info, Employee>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PayrollPlan::LEN,
+        seeds = [PAYROLL_PLAN_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump
+    )]
+    pub payroll_plan: Account<'info, PayrollPlan>,
+
+    /// Company authority (must sign and pay)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<ApprovePayrollPlan>,
+    amount: u64,
+    amount_commitment: [u8; 32],
+    amount_nonce: [u8; 32],
+    salary_nonce: [u8; 32],
+    periods: u16,
+    keeper_tip: u64,
+) -> Result<()> {
+    verify_amount_commitment(&amount_commitment, amount, &amount_nonce)?;
+
+    // Plans pay the committed salary for full periods (prorated when cranked)
+    verify_salary_commitment(&ctx.accounts.employee.salary_commitment, amount, &salary_nonce)?;
+
+    // Keeper tips are capped relative to the planned payment
+    let max_keeper_tip = (amount as u128)
+        .checked_mul(MAX_KEEPER_TIP_BPS as u128)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?
+        / BPS_DENOMINATOR as u128;
+    require!(
+        amount >= MIN_SALARY_AMOUNT && periods > 0 && keeper_tip as u128 <= max_keeper_tip,
+        GhostPayrollError::InvalidPayrollPlan
+    );
+
+    let employee = &ctx.accounts.employee;
+    let payroll_plan = &mut ctx.accounts.payroll_plan;
+    let clock = Clock::get()?;
+
+    payroll_plan.company = ctx.accounts.company.key();
+    payroll_plan.employee = employee.wallet;
+    payroll_plan.mint = employee.payout_mint;
+    payroll_plan.amount = amount;
+    payroll_plan.amount_commitment = amount_commitment;
+    payroll_plan.salary_commitment = employee.salary_commitment;
+    payroll_plan.periods_approved = periods;
+    payroll_plan.periods_paid = 0;
+    payroll_plan.keeper_tip = keeper_tip;
+    payroll_plan.approved_at = clock.unix_timestamp;
+    payroll_plan.bump = ctx.bumps.payroll_plan;
+
+    emit!(PayrollPlanApproved {
+        company: payroll_plan.company,
+        employee: payroll_plan.employee,
+        mint: payroll_plan.mint,
+        amount_commitment,
+        periods_approved: periods,
+        keeper_tip,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Payroll plan approved");
+    msg!("Employee: {}", payroll_plan.employee);
+    msg!("Periods: {}", periods);
+    msg!("Keeper tip: {}", keeper_tip);
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CancelPaymentCorrection<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [PAYMENT_CORRECTION_SEED, payment_correction.payment_proof.as_ref()],
+        bump = payment_correction.bump,
+        constraint = payment_correction.company == company.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = payment_correction.status == CorrectionStatus::Pending @ GhostPayrollError::CorrectionNotPending
+    )]
+    pub payment_correction: Account<'info, PaymentCorrection>,
+
+    /// Company authority (must sign, receives rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<CancelPaymentCorrection>) -> Result<()> {
+    let payment_correction = &ctx.accounts.payment_correction;
+    let clock = Clock::get()?;
+
+    emit!(PaymentCorrectionCancelled {
+        company: payment_correction.company,
+        employee: payment_correction.employee,
+        payment_id: payment_correction.payment_id,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Payment correction cancelled");
+    msg!("Payment ID: {}", payment_correction.payment_id);
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        mut,
+        seeds = [
+            WITHDRAWAL_SEED,
+            company.key().as_ref(),
+            &withdrawal.withdrawal_id.to_le_bytes()
+        ],
+        bump = withdrawal.bump,
+        constraint = withdrawal.company == company.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = withdrawal.status == WithdrawalStatus::Pending @ GhostPayrollError::WithdrawalNotPending
+    )]
+    pub withdrawal: Account<'info, TreasuryWithdrawal>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<CancelWithdrawal>) -> Result<()> {
+    let company = &mut ctx.accounts.company;
+    let withdrawal = &mut ctx.accounts.withdrawal;
+    let clock = Clock::get()?;
+
+    withdrawal.status = WithdrawalStatus::Cancelled;
+    company.pending_withdrawals = company
+        .pending_withdrawals
+        .checked_sub(1)
+        .ok_or(GhostPayrollError::ArithmeticUnderflow)?;
+
+    emit!(WithdrawalCancelled {
+        company: withdrawal.company,
+        withdrawal_id: withdrawal.withdrawal_id,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Treasury withdrawal cancelled");
+    msg!("Withdrawal ID: {}", withdrawal.withdrawal_id);
+
+    Ok(())
+}
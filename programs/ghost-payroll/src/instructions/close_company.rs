@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CloseCompany<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.employee_count == 0 @ GhostPayrollError::ActiveEmployeesRemain,
+        constraint = company.pending_final_payments == 0 @ GhostPayrollError::FinalPaymentsPending,
+        constraint = company.pending_stream_withdrawals == 0 @ GhostPayrollError::StreamWithdrawalsPending,
+        constraint = company.withholding_vault_count == 0 @ GhostPayrollError::WithholdingVaultsRemain,
+        constraint = company.pending_withdrawals == 0 @ GhostPayrollError::WithdrawalsPending,
+        constraint = company.treasury_count == 0 @ GhostPayrollError::TreasuriesRemain,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Company authority (must sign, receives rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<CloseCompany>) -> Result<()> {
+    let company = &ctx.accounts.company;
+    let clock = Clock::get()?;
+
+    emit!(CompanyClosed {
+        company: company.key(),
+        authority: company.authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Company closed: {}", company.name);
+    msg!("Total payments made: {}", company.total_payments_made);
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::zk_token_proof_program;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+use crate::confidential::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+use crate::transfers::*;
+
+#[derive(Accounts)]
+pub struct CloseTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.employee_count == 0 @ GhostPayrollError::ActiveEmployeesRemain,
+        constraint = company.pending_final_payments == 0 @ GhostPayrollError::FinalPaymentsPending,
+        constraint = company.pending_stream_withdrawals == 0 @ GhostPayrollError::StreamWithdrawalsPending,
+        constraint = company.withholding_vault_count == 0 @ GhostPayrollError::WithholdingVaultsRemain,
+        constraint = company.pending_withdrawals == 0 @ GhostPayrollError::WithdrawalsPending,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Company treasury token account being closed
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, company.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Accounting record for the treasury (rent returned to the authority)
+    #[account(
+        mut,
+        close = authority,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), mint.key().as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Box<Account<'info, TreasuryLedger>>,
+
+    /// Token mint of the treasury
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Authority's token account receiving the remaining treasury balance
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = authority_token_account.mint == mint.key() @ GhostPayrollError::InvalidTokenMint
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Context state of a `VerifyZeroBalance` proof over the treasury's
+    /// encrypted balance (required once the treasury is confidential)
+    /// CHECK: Owner is the proof program, contents are validated by the token program
+    #[account(owner = zk_token_proof_program::id() @ GhostPayrollError::InvalidTransferProof)]
+    pub zero_balance_proof: Option<UncheckedAccount<'info>>,
+
+    /// Company authority (must sign, receives rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// A confidential treasury must have its encrypted balance unshielded first;
+/// the zero balance proof shows nothing is left behind when it is emptied.
+pub(crate) fn handler(ctx: Context<CloseTreasury>) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+    let remaining_balance = ctx.accounts.treasury.amount;
+
+    // Token-2022 only closes confidential accounts whose encrypted balances are zeroed
+    if is_confidential(&ctx.accounts.treasury.to_account_info())? {
+        let zero_balance_proof = ctx
+            .accounts
+            .zero_balance_proof
+            .as_ref()
+            .ok_or(GhostPayrollError::ConfidentialBalanceRemains)?;
+        let treasury = ConfidentialTreasury {
+            company: &ctx.accounts.company,
+            treasury: &ctx.accounts.treasury,
+            mint: &ctx.accounts.mint,
+            token_program: ctx.accounts.token_program.to_account_info(),
+        };
+        treasury.empty(zero_balance_proof.to_account_info())?;
+    }
+
+    // Return the remaining balance to the authority (still bound by the spending cap)
+    if remaining_balance > 0 {
+        ctx.accounts
+            .treasury_ledger
+            .record_outflow(remaining_balance, current_time)?;
+
+        transfer_from_treasury(
+            &ctx.accounts.token_program,
+            &ctx.accounts.treasury,
+            &ctx.accounts.mint,
+            ctx.accounts.authority_token_account.to_account_info(),
+            &ctx.accounts.company,
+            remaining_balance,
+        )?;
+    }
+
+    // Token-2022 refuses to close accounts still holding withheld transfer fees
+    harvest_withheld_fees(
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        ctx.accounts.treasury.to_account_info(),
+    )?;
+
+    // Close the treasury token account, returning its rent to the authority
+    let company_seeds = &[
+        COMPANY_SEED,
+        ctx.accounts.company.authority.as_ref(),
+        &[ctx.accounts.company.bump],
+    ];
+    let signer_seeds = &[&company_seeds[..]];
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.treasury.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.company.to_account_info(),
+        },
+        signer_seeds,
+    );
+
+    token_interface::close_account(close_ctx)?;
+
+    // Closing the last treasury winds the company down
+    let company = &mut ctx.accounts.company;
+    company.treasury_count = company
+        .treasury_count
+        .checked_sub(1)
+        .ok_or(GhostPayrollError::ArithmeticUnderflow)?;
+    if company.treasury_count == 0 {
+        company.is_active = false;
+    }
+
+    emit!(TreasuryClosed {
+        company: company.key(),
+        mint: ctx.accounts.mint.key(),
+        amount_returned: remaining_balance,
+        destination: ctx.accounts.authority_token_account.key(),
+        timestamp: current_time,
+    });
+
+    msg!("Treasury closed for company: {}", company.name);
+    msg!("Mint: {}", ctx.accounts.mint.key());
+    msg!("Returned to authority: {}", remaining_balance);
+    msg!("Treasuries remaining: {}", company.treasury_count);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CloseVerifyingKey<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Verifying key being closed (rent returned to the authority)
+    #[account(
+        mut,
+        close = authority,
+        seeds = [VERIFYING_KEY_SEED, company.key().as_ref()],
+        bump = verifying_key.bump,
+        constraint = verifying_key.company == company.key() @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub verifying_key: Box<Account<'info, PaymentVerifyingKey>>,
+
+    /// Company authority (must sign, receives rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// No further payment proofs can be recorded until a key is registered again
+pub(crate) fn handler(ctx: Context<CloseVerifyingKey>) -> Result<()> {
+    let clock = Clock::get()?;
+    let verifying_key = &ctx.accounts.verifying_key;
+
+    emit!(VerifyingKeyClosed {
+        company: verifying_key.company,
+        verifying_key: verifying_key.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Payment verifying key closed");
+    msg!("Company: {}", verifying_key.company);
+
+    Ok(())
+}
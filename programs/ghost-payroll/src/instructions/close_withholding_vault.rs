@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+use crate::transfers::*;
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CloseWithholdingVault<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Token mint withholdings are held in
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Withholding vault being closed
+    #[account(
+        mut,
+        seeds = [WITHHOLDING_VAULT_SEED, company.key().as_ref(), mint.key().as_ref(), name.as_bytes()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Company authority (must sign, receives rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withheld funds belong to tax authorities and benefits providers, so a
+/// vault only closes once everything in it has been remitted.
+pub(crate) fn handler(ctx: Context<CloseWithholdingVault>, name: String) -> Result<()> {
+    require!(
+        ctx.accounts.vault.amount == 0,
+        GhostPayrollError::WithholdingVaultNotEmpty
+    );
+
+    let clock = Clock::get()?;
+
+    // Token-2022 refuses to close accounts still holding withheld transfer fees
+    harvest_withheld_fees(
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        ctx.accounts.vault.to_account_info(),
+    )?;
+
+    // Close the vault token account, returning its rent to the authority
+    let company_seeds = &[
+        COMPANY_SEED,
+        ctx.accounts.company.authority.as_ref(),
+        &[ctx.accounts.company.bump],
+    ];
+    let signer_seeds = &[&company_seeds[..]];
+
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.company.to_account_info(),
+        },
+        signer_seeds,
+    );
+
+    token_interface::close_account(close_ctx)?;
+
+    let company = &mut ctx.accounts.company;
+    company.withholding_vault_count = company
+        .withholding_vault_count
+        .checked_sub(1)
+        .ok_or(GhostPayrollError::ArithmeticUnderflow)?;
+
+    emit!(WithholdingVaultClosed {
+        company: company.key(),
+        mint: ctx.accounts.mint.key(),
+        vault: ctx.accounts.vault.key(),
+        name: name.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Withholding vault closed: {}", name);
+    msg!("Vaults remaining: {}", company.withholding_vault_count);
+
+    Ok(())
+}
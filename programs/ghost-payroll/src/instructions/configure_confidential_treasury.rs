@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::zk_token_proof_program;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::confidential::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ConfigureConfidentialTreasury<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Company treasury token account for a Token-2022 mint with confidential transfers
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, company.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint of the treasury
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Context state of a `VerifyPubkeyValidity` proof for the treasury's ElGamal key
+    /// CHECK: Owner is the proof program, contents are validated by the token program
+    #[account(owner = zk_token_proof_program::id() @ GhostPayrollError::InvalidTransferProof)]
+    pub pubkey_validity_proof: UncheckedAccount<'info>,
+
+    /// Company authority (must sign and pays for the larger treasury)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<ConfigureConfidentialTreasury>,
+    decryptable_zero_balance: [u8; 36],
+    maximum_pending_balance_credit_counter: u64,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let clock = Clock::get()?;
+
+    let treasury = ConfidentialTreasury {
+        company: &accounts.company,
+        treasury: &accounts.treasury,
+        mint: &accounts.mint,
+        token_program: accounts.token_program.to_account_info(),
+    };
+    treasury.configure(
+        accounts.authority.to_account_info(),
+        accounts.system_program.to_account_info(),
+        accounts.pubkey_validity_proof.to_account_info(),
+        decryptable_zero_balance,
+        maximum_pending_balance_credit_counter,
+    )?;
+
+    emit!(ConfidentialTreasuryConfigured {
+        company: accounts.company.key(),
+        mint: accounts.mint.key(),
+        treasury: accounts.treasury.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Confidential transfers enabled for treasury: {}",
+        accounts.treasury.key()
+    );
+    msg!("Mint: {}", accounts.mint.key());
+
+    Ok(())
+}
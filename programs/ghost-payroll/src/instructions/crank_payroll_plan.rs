@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::payroll::*;
+use crate::state::*;
+use crate::transfers::*;
+
+#[derive(Accounts)]
+pub struct CrankPayrollPlan<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.authority.as_ref()],
+        bump = company.bump,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = employee.bump,
+        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound,
+        constraint = employee.is_active @ GhostPayrollError::EmployeeInactive
+    )]
+    pub employee: Account<'info, Employee>,
+
+    /// Pre-approved plan fixing the recipient and amount
+    #[account(
+        mut,
+        seeds = [PAYROLL_PLAN_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = payroll_plan.bump,
+        constraint = payroll_plan.mint == employee.payout_mint @ GhostPayrollError::InvalidTokenMint,
+        constraint = payroll_plan.salary_commitment == employee.salary_commitment @ GhostPayrollError::SalaryCommitmentMismatch,
+        constraint = payroll_plan.periods_paid < payroll_plan.periods_approved @ GhostPayrollError::PayrollPlanExhausted
+    )]
+    pub payroll_plan: Account<'info, PayrollPlan>,
+
+    /// Company treasury token account for the employee's payout mint
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, company.key().as_ref(), employee.payout_mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Accounting record for the employee's payout treasury
+    #[account(
+        mut,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), employee.payout_mint.as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Box<Account<'info, TreasuryLedger>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Protocol fee vault for the payout mint
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, employee.payout_mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Open payroll run to record this payment against (required for salary while a run is open)
+    #[account(
+        mut,
+        seeds = [PAYROLL_RUN_SEED, company.key().as_ref(), &payroll_run.period.to_le_bytes()],
+        bump = payroll_run.bump,
+        constraint = payroll_run.status == PayrollRunStatus::Open @ GhostPayrollError::PayrollRunNotOpen
+    )]
+    pub payroll_run: Option<Box<Account<'info, PayrollRun>>>,
+
+    /// Employee's token account for receiving payment
+    #[account(
+        mut,
+        constraint = employee_token_account.owner == employee.wallet @ GhostPayrollError::InvalidTokenMint,
+        constraint = employee_token_account.mint == employee.payout_mint @ GhostPayrollError::InvalidTokenMint
+    )]
+    pub employee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Keeper's token account for the tip (optional)
+    #[account(
+        mut,
+        constraint = keeper_token_account.mint == employee.payout_mint @ GhostPayrollError::InvalidTokenMint
+    )]
+    pub keeper_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Outstanding advance, required while the employee is repaying one (optional)
+    #[account(
+        mut,
+        seeds = [ADVANCE_SEED, employee.key().as_ref()],
+        bump = advance.bump
+    )]
+    pub advance: Option<Box<Account<'info, Advance>>>,
+
+    /// Company authority, refunded the rent of an advance this crank repays (optional)
+    #[account(
+        mut,
+        address = company.authority @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub company_authority: Option<SystemAccount<'info>>,
+
+    /// Token mint the employee is paid in
+    #[account(
+        address = employee.payout_mint @ GhostPayrollError::InvalidTokenMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Any wallet cranking the due payment (must sign)
+    pub keeper: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Remaining accounts are the employee's withholding vaults, one per
+/// withholding line and in the same order.
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CrankPayrollPlan<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    let withholding_vaults = ctx.remaining_accounts;
+    let accounts = ctx.accounts;
+    let full_period_amount = accounts.payroll_plan.amount;
+    let amount = accounts
+        .employee
+        .proration_basis()
+        .prorate(full_period_amount)?;
+
+    let mut payroll = PayrollAccounts {
+        company: &mut accounts.company,
+        treasury: &mut accounts.treasury,
+        treasury_ledger: &mut accounts.treasury_ledger,
+        protocol_config: &accounts.protocol_config,
+        fee_vault: &accounts.fee_vault,
+        mint: &accounts.mint,
+        token_program: &accounts.token_program,
+        payroll_run: accounts.payroll_run.as_deref_mut().map(|run| &mut **run),
+    };
+
+    // Keepers only ever pay the planned amount (prorated for partial periods), on schedule
+    let payout = pay_employee(
+        &mut payroll,
+        &mut accounts.employee,
+        accounts.advance.as_deref_mut().map(|advance| &mut **advance),
+        accounts.employee_token_account.to_account_info(),
+        withholding_vaults,
+        PaymentRequest {
+            amount,
+            full_period_amount,
+            category: PaymentCategory::Salary,
+            // The plan commits to the full-period amount, which a prorated payment does not open
+            amount_commitment: (amount == full_period_amount)
+                .then_some(accounts.payroll_plan.amount_commitment),
+        },
+        current_time,
+    )?;
+
+    // A repaid advance is closed and its rent returned to the authority
+    if payout.advance_repaid {
+        if let Some(advance) = &accounts.advance {
+            let company_authority = accounts
+                .company_authority
+                .as_ref()
+                .ok_or(GhostPayrollError::AdvanceAccountRequired)?;
+            advance.close(company_authority.to_account_info())?;
+        }
+    }
+
+    // Tip the keeper out of the treasury
+    let payroll_plan = &mut accounts.payroll_plan;
+    let keeper_tip = match &accounts.keeper_token_account {
+        Some(keeper_token_account) if payroll_plan.keeper_tip > 0 => {
+            require!(
+                accounts.treasury.amount >= payroll_plan.keeper_tip,
+                GhostPayrollError::InsufficientCompanyBalance
+            );
+            accounts
+                .treasury_ledger
+                .record_outflow(payroll_plan.keeper_tip, current_time)?;
+
+            transfer_from_treasury(
+                &accounts.token_program,
+                &accounts.treasury,
+                &accounts.mint,
+                keeper_token_account.to_account_info(),
+                &accounts.company,
+                payroll_plan.keeper_tip,
+            )?;
+            payroll_plan.keeper_tip
+        }
+        _ => 0,
+    };
+
+    payroll_plan.periods_paid = payroll_plan
+        .periods_paid
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    emit!(PayrollPlanCranked {
+        company: payroll_plan.company,
+        employee: payroll_plan.employee,
+        keeper: accounts.keeper.key(),
+        keeper_tip,
+        periods_paid: payroll_plan.periods_paid,
+        periods_approved: payroll_plan.periods_approved,
+        timestamp: current_time,
+    });
+
+    msg!("Payroll plan cranked");
+    msg!("Employee: {}", payroll_plan.employee);
+    msg!("Keeper: {}", accounts.keeper.key());
+    msg!("Keeper tip: {}", keeper_tip);
+    msg!(
+        "Periods paid: {}/{}",
+        payroll_plan.periods_paid,
+        payroll_plan.periods_approved
+    );
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreateWithholdingVault<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Token mint withholdings are held in
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Withholding vault token account (owned by company PDA)
+    #[account(
+        init,
+        payer = authority,
+        seeds = [WITHHOLDING_VAULT_SEED, company.key().as_ref(), mint.key().as_ref(), name.as_bytes()],
+        bump,
+        token::mint = mint,
+        token::authority = company,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Company authority (must sign and pay)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn handler(ctx: Context<CreateWithholdingVault>, name: String) -> Result<()> {
+    require!(
+        !name.is_empty() && name.len() <= MAX_WITHHOLDING_NAME_LENGTH,
+        GhostPayrollError::InvalidWithholdingLine
+    );
+
+    let clock = Clock::get()?;
+
+    let company = &mut ctx.accounts.company;
+    company.withholding_vault_count = company
+        .withholding_vault_count
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    emit!(WithholdingVaultCreated {
+        company: company.key(),
+        mint: ctx.accounts.mint.key(),
+        vault: ctx.accounts.vault.key(),
+        name: name.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Withholding vault created: {}", name);
+    msg!("Mint: {}", ctx.accounts.mint.key());
+    msg!("Vault: {}", ctx.accounts.vault.key());
+
+    Ok(())
+}
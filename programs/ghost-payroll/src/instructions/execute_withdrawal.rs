@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+use crate::transfers::*;
+
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            WITHDRAWAL_SEED,
+            company.key().as_ref(),
+            &withdrawal.withdrawal_id.to_le_bytes()
+        ],
+        bump = withdrawal.bump,
+        constraint = withdrawal.company == company.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = withdrawal.status == WithdrawalStatus::Pending @ GhostPayrollError::WithdrawalNotPending
+    )]
+    pub withdrawal: Account<'info, TreasuryWithdrawal>,
+
+    /// Treasury or withholding vault recorded on the withdrawal request
+    #[account(
+        mut,
+        address = withdrawal.source @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    /// Accounting record for the treasury
+    #[account(
+        mut,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), withdrawal.mint.as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Box<Account<'info, TreasuryLedger>>,
+
+    /// Token account recorded on the withdrawal request
+    #[account(
+        mut,
+        address = withdrawal.destination @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint of the treasury
+    #[account(
+        address = withdrawal.mint @ GhostPayrollError::InvalidTokenMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub(crate) fn handler(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+    let company = &mut ctx.accounts.company;
+    let withdrawal = &mut ctx.accounts.withdrawal;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Enforce the timelock
+    require!(
+        current_time >= withdrawal.executable_at,
+        GhostPayrollError::WithdrawalNotReady
+    );
+
+    let balance_before = ctx.accounts.source.amount;
+    require!(
+        balance_before >= withdrawal.amount,
+        GhostPayrollError::InsufficientCompanyBalance
+    );
+    let balance_after = balance_before - withdrawal.amount;
+
+    // Enforce the rolling spending cap
+    ctx.accounts
+        .treasury_ledger
+        .record_outflow(withdrawal.amount, current_time)?;
+
+    // Transfer-fee mints credit the destination with less than was withdrawn
+    let fee_withheld = transfer_fee(&ctx.accounts.mint, withdrawal.amount)?;
+
+    // Transfer tokens from treasury to destination
+    transfer_from_treasury(
+        &ctx.accounts.token_program,
+        &ctx.accounts.source,
+        &ctx.accounts.mint,
+        ctx.accounts.destination.to_account_info(),
+        company,
+        withdrawal.amount,
+    )?;
+
+    withdrawal.status = WithdrawalStatus::Executed;
+    company.pending_withdrawals = company
+        .pending_withdrawals
+        .checked_sub(1)
+        .ok_or(GhostPayrollError::ArithmeticUnderflow)?;
+
+    emit!(WithdrawalExecuted {
+        company: company.key(),
+        withdrawal_id: withdrawal.withdrawal_id,
+        mint: withdrawal.mint,
+        amount: withdrawal.amount,
+        fee_withheld,
+        destination: withdrawal.destination,
+        timestamp: current_time,
+    });
+
+    // Warn when this withdrawal leaves the treasury short of its committed runway
+    let treasury_ledger = &ctx.accounts.treasury_ledger;
+    if withdrawal.source == treasury_ledger.treasury
+        && treasury_ledger.crossed_low_runway(balance_before, balance_after)
+    {
+        emit!(LowRunway {
+            company: company.key(),
+            mint: treasury_ledger.mint,
+            treasury_balance: balance_after,
+            period_obligation: treasury_ledger.period_obligation,
+            runway_periods: treasury_ledger.runway_periods(balance_after),
+            low_runway_threshold: treasury_ledger.low_runway_threshold,
+            timestamp: current_time,
+        });
+    }
+
+    msg!("Treasury withdrawal executed");
+    msg!("Withdrawal ID: {}", withdrawal.withdrawal_id);
+    msg!("Amount: {} (fee withheld: {})", withdrawal.amount, fee_withheld);
+    msg!("Destination: {}", withdrawal.destination);
+
+    Ok(())
+}
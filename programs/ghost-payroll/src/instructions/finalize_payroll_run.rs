@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct FinalizePayrollRun<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [PAYROLL_RUN_SEED, company.key().as_ref(), &payroll_run.period.to_le_bytes()],
+        bump = payroll_run.bump,
+        constraint = payroll_run.status == PayrollRunStatus::Open @ GhostPayrollError::PayrollRunNotOpen
+    )]
+    pub payroll_run: Box<Account<'info, PayrollRun>>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<FinalizePayrollRun>) -> Result<()> {
+    let company = &mut ctx.accounts.company;
+    let payroll_run = &mut ctx.accounts.payroll_run;
+    let clock = Clock::get()?;
+
+    payroll_run.status = PayrollRunStatus::Finalized;
+    payroll_run.finalized_at = clock.unix_timestamp;
+    company.has_open_payroll_run = false;
+
+    emit!(PayrollRunFinalized {
+        company: company.key(),
+        period: payroll_run.period,
+        payments_made: payroll_run.payments_made,
+        total_paid: payroll_run.total_paid,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Payroll run finalized for company: {}", company.name);
+    msg!("Period: {}", payroll_run.period);
+    msg!("Employees paid: {}", payroll_run.payments_made);
+
+    Ok(())
+}
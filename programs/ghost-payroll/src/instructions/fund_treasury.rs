@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+use crate::transfers::*;
+
+#[derive(Accounts)]
+pub struct FundTreasury<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.authority.as_ref()],
+        bump = company.bump,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Company treasury token account for the deposited mint
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, company.key().as_ref(), treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Accounting record for the treasury
+    #[account(
+        mut,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), treasury.mint.as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Account<'info, TreasuryLedger>,
+
+    /// Funder's token account the deposit is taken from
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == funder.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = funder_token_account.mint == treasury.mint @ GhostPayrollError::InvalidTokenMint
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint of the treasury
+    #[account(
+        address = treasury.mint @ GhostPayrollError::InvalidTokenMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Wallet funding the treasury (must sign)
+    pub funder: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub(crate) fn handler(ctx: Context<FundTreasury>, amount: u64) -> Result<()> {
+    require!(amount > 0, GhostPayrollError::InvalidDepositAmount);
+
+    // Transfer-fee mints credit the treasury with less than was sent
+    let fee_withheld = transfer_fee(&ctx.accounts.mint, amount)?;
+    let amount_received = amount
+        .checked_sub(fee_withheld)
+        .ok_or(GhostPayrollError::ArithmeticUnderflow)?;
+
+    // Transfer tokens from funder to treasury
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        },
+    );
+
+    token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    let company = &ctx.accounts.company;
+    let treasury_ledger = &mut ctx.accounts.treasury_ledger;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Update treasury funding records
+    treasury_ledger.total_deposited = treasury_ledger
+        .total_deposited
+        .checked_add(amount_received)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    treasury_ledger.deposit_count = treasury_ledger
+        .deposit_count
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    treasury_ledger.last_deposit_timestamp = current_time;
+
+    emit!(TreasuryFunded {
+        company: company.key(),
+        funder: ctx.accounts.funder.key(),
+        mint: treasury_ledger.mint,
+        amount,
+        fee_withheld,
+        total_deposited: treasury_ledger.total_deposited,
+        deposit_count: treasury_ledger.deposit_count,
+        timestamp: current_time,
+    });
+
+    msg!("Treasury funded for company: {}", company.name);
+    msg!("Funder: {}", ctx.accounts.funder.key());
+    msg!("Mint: {}", treasury_ledger.mint);
+    msg!("Amount: {} (fee withheld: {})", amount, fee_withheld);
+    msg!("Deposit #{}", treasury_ledger.deposit_count);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct GetRunway<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, company.authority.as_ref()],
+        bump = company.bump
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), treasury_ledger.mint.as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Account<'info, TreasuryLedger>,
+
+    /// Company treasury token account for the ledger's mint
+    #[account(
+        seeds = [TREASURY_SEED, company.key().as_ref(), treasury_ledger.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Returns the number of full pay periods the treasury can cover
+/// (u64::MAX when no obligation has been committed)
+pub(crate) fn handler(ctx: Context<GetRunway>) -> Result<u64> {
+    let treasury_ledger = &ctx.accounts.treasury_ledger;
+    let balance = ctx.accounts.treasury.amount;
+    let runway_periods = treasury_ledger.runway_periods(balance);
+
+    msg!("Treasury balance: {}", balance);
+    msg!("Obligation per period: {}", treasury_ledger.period_obligation);
+    msg!("Runway: {} periods", runway_periods);
+
+    Ok(runway_periods)
+}
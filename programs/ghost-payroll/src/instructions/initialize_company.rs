@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+use crate::commitment::*;
 use crate::constants::*;
 use crate::errors::*;
 use crate::state::*;
@@ -17,30 +18,49 @@ pub struct InitializeCompany<'info> {
     )]
     pub company: Account<'info, Company>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     /// Company admin/owner wallet
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    /// Token mint for payments (e.g., USDC)
-    pub payment_token: Account<'info, Mint>,
+    /// Token mint for payments (e.g., USDC), owned by either token program
+    #[account(mint::token_program = token_program)]
+    pub payment_token: InterfaceAccount<'info, Mint>,
 
-    /// Company treasury token account (owned by company PDA)
+    /// Company treasury token account for the default mint (owned by company PDA)
     #[account(
         init,
         payer = authority,
-        seeds = [TREASURY_SEED, company.key().as_ref()],
+        seeds = [TREASURY_SEED, company.key().as_ref(), payment_token.key().as_ref()],
         bump,
         token::mint = payment_token,
         token::authority = company,
+        token::token_program = token_program,
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Accounting record for the default treasury
+    #[account(
+        init,
+        payer = authority,
+        space = TreasuryLedger::LEN,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), payment_token.key().as_ref()],
+        bump
     )]
-    pub treasury: Account<'info, TokenAccount>,
+    pub treasury_ledger: Account<'info, TreasuryLedger>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<InitializeCompany>,
     name: String,
     budget_commitment: [u8; 32],
@@ -51,6 +71,7 @@ pub fn handler(
         name.len() <= MAX_COMPANY_NAME_LENGTH && !name.is_empty(),
         GhostPayrollError::CompanyNameTooLong
     );
+    validate_pedersen_commitment(&budget_commitment)?;
 
     let company = &mut ctx.accounts.company;
     let clock = Clock::get()?;
@@ -62,13 +83,46 @@ pub fn handler(
     company.employee_count = 0;
     company.budget_commitment = budget_commitment;
     company.payment_token = ctx.accounts.payment_token.key();
+    company.treasury_count = 1;
+    company.pending_final_payments = 0;
+    company.pending_stream_withdrawals = 0;
+    company.withholding_vault_count = 0;
+    company.pending_withdrawals = 0;
     company.payment_frequency = payment_frequency;
+    company.pay_anchor = current_time;
     company.last_payment_timestamp = current_time;
-    company.next_payment_due = current_time + payment_frequency.seconds_to_next_payment();
+    company.next_payment_due = payment_frequency.next_payment_after(current_time, current_time);
     company.total_payments_made = 0;
+    company.withdrawal_delay = DEFAULT_WITHDRAWAL_DELAY;
+    company.withdrawal_count = 0;
+    company.paused = false;
+    company.pause_reason = 0;
+    company.paused_at = 0;
+    company.next_employee_index = 0;
+    company.payroll_run_count = 0;
+    company.has_open_payroll_run = false;
+    company.salary_commitment_total = [0; 32];
+    company.budget_verified_at = 0;
     company.is_active = true;
     company.bump = ctx.bumps.company;
 
+    // Initialize default treasury ledger
+    let treasury_ledger = &mut ctx.accounts.treasury_ledger;
+    treasury_ledger.company = company.key();
+    treasury_ledger.mint = company.payment_token;
+    treasury_ledger.treasury = ctx.accounts.treasury.key();
+    treasury_ledger.total_deposited = 0;
+    treasury_ledger.deposit_count = 0;
+    treasury_ledger.last_deposit_timestamp = 0;
+    treasury_ledger.period_obligation = 0;
+    treasury_ledger.low_runway_threshold = DEFAULT_LOW_RUNWAY_THRESHOLD;
+    treasury_ledger.spending_cap = 0;
+    treasury_ledger.pending_spending_cap = 0;
+    treasury_ledger.pending_cap_effective_at = 0;
+    treasury_ledger.outflow_day = 0;
+    treasury_ledger.outflow_buckets = [0; SPENDING_CAP_WINDOW_DAYS];
+    treasury_ledger.bump = ctx.bumps.treasury_ledger;
+
     msg!("Company initialized: {}", company.name);
     msg!("Authority: {}", company.authority);
     msg!("Payment token: {}", company.payment_token);
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitializeFeeVault<'info> {
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Token mint the vault collects fees in
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Fee vault token account (owned by protocol config PDA)
+    #[account(
+        init,
+        payer = payer,
+        seeds = [FEE_VAULT_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = protocol_config,
+        token::token_program = token_program,
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Anyone may pay to open a fee vault; funds can only leave via sweep_fees
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub(crate) fn handler(ctx: Context<InitializeFeeVault>) -> Result<()> {
+    msg!("Fee vault initialized");
+    msg!("Mint: {}", ctx.accounts.mint.key());
+    msg!("Vault: {}", ctx.accounts.fee_vault.key());
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::program::GhostPayroll;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ProtocolConfig::LEN,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Program upgrade authority (must sign and pay)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub program: Program<'info, GhostPayroll>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<InitializeProtocolConfig>) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+
+    protocol_config.admin = ctx.accounts.admin.key();
+    protocol_config.fee_bps = PAYMENT_PROCESSING_FEE_BPS;
+    protocol_config.guardian = ctx.accounts.admin.key();
+    protocol_config.paused = false;
+    protocol_config.pause_reason = 0;
+    protocol_config.paused_at = 0;
+    protocol_config.bump = ctx.bumps.protocol_config;
+
+    msg!("Protocol config initialized");
+    msg!("Admin: {}", protocol_config.admin);
+    msg!("Payment fee: {} bps", protocol_config.fee_bps);
+    msg!("Guardian: {}", protocol_config.guardian);
+
+    Ok(())
+}
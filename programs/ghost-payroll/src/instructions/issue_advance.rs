@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::payroll::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct IssueAdvance<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = employee.bump,
+        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound,
+        constraint = employee.is_active @ GhostPayrollError::EmployeeInactive
+    )]
+    pub employee: Account<'info, Employee>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Advance::LEN,
+        seeds = [ADVANCE_SEED, employee.key().as_ref()],
+        bump
+    )]
+    pub advance: Box<Account<'info, Advance>>,
+
+    /// Company treasury token account for the employee's payout mint
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, company.key().as_ref(), employee.payout_mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Accounting record for the employee's payout treasury
+    #[account(
+        mut,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), employee.payout_mint.as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Box<Account<'info, TreasuryLedger>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Protocol fee vault for the payout mint
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, employee.payout_mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Employee's token account for receiving the advance
+    #[account(
+        mut,
+        constraint = employee_token_account.owner == employee.wallet @ GhostPayrollError::InvalidTokenMint,
+        constraint = employee_token_account.mint == employee.payout_mint @ GhostPayrollError::InvalidTokenMint
+    )]
+    pub employee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint the employee is paid in
+    #[account(
+        address = employee.payout_mint @ GhostPayrollError::InvalidTokenMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Company authority (must sign and pay)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<IssueAdvance>, amount: u64, repayment_per_period: u64) -> Result<()> {
+    // Validate the advance and its repayment schedule
+    require!(amount >= MIN_SALARY_AMOUNT, GhostPayrollError::InvalidAdvance);
+    require!(
+        repayment_per_period > 0 && repayment_per_period <= amount,
+        GhostPayrollError::InvalidAdvance
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    let accounts = ctx.accounts;
+    let employee = &mut accounts.employee;
+
+    let mut payroll = PayrollAccounts {
+        company: &mut accounts.company,
+        treasury: &mut accounts.treasury,
+        treasury_ledger: &mut accounts.treasury_ledger,
+        protocol_config: &accounts.protocol_config,
+        fee_vault: &accounts.fee_vault,
+        mint: &accounts.mint,
+        token_program: &accounts.token_program,
+        payroll_run: None,
+    };
+
+    let disbursement = disburse(
+        &mut payroll,
+        employee.wallet,
+        accounts.employee_token_account.to_account_info(),
+        amount,
+        current_time,
+    )?;
+
+    let advance = &mut accounts.advance;
+    advance.company = accounts.company.key();
+    advance.employee = employee.wallet;
+    advance.mint = employee.payout_mint;
+    advance.principal = amount;
+    advance.outstanding = amount;
+    advance.repayment_per_period = repayment_per_period;
+    advance.issued_at = current_time;
+    advance.bump = ctx.bumps.advance;
+
+    // Salary payments now withhold repayments until the advance is cleared
+    employee.has_advance = true;
+
+    emit!(AdvanceIssued {
+        company: advance.company,
+        employee: advance.employee,
+        mint: advance.mint,
+        principal: amount,
+        repayment_per_period,
+        timestamp: current_time,
+    });
+
+    msg!("Advance issued");
+    msg!("Employee: {}", advance.employee);
+    msg!("Principal: {}", amount);
+    msg!("Repayment per period: {}", repayment_per_period);
+    msg!("Protocol fee: {}", disbursement.protocol_fee);
+
+    Ok(())
+}
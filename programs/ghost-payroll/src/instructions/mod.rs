@@ -4,6 +4,56 @@ pub mod update_employee_salary;
 pub mod remove_employee;
 pub mod process_payment;
 pub mod record_payment_proof;
+pub mod fund_treasury;
+pub mod queue_withdrawal;
+pub mod execute_withdrawal;
+pub mod cancel_withdrawal;
+pub mod set_withdrawal_delay;
+pub mod add_treasury_mint;
+pub mod set_payroll_obligation;
+pub mod get_runway;
+pub mod set_spending_cap;
+pub mod initialize_protocol_config;
+pub mod update_protocol_fee;
+pub mod initialize_fee_vault;
+pub mod sweep_fees;
+pub mod close_treasury;
+pub mod close_company;
+pub mod set_guardian;
+pub mod pause_protocol;
+pub mod unpause_protocol;
+pub mod pause_company;
+pub mod unpause_company;
+pub mod open_payment_correction;
+pub mod accept_payment_correction;
+pub mod cancel_payment_correction;
+pub mod process_payroll_batch;
+pub mod open_payroll_run;
+pub mod resume_payroll_run;
+pub mod finalize_payroll_run;
+pub mod approve_payroll_plan;
+pub mod crank_payroll_plan;
+pub mod revoke_payroll_plan;
+pub mod set_stream_rate;
+pub mod set_stream_paused;
+pub mod withdraw_accrued;
+pub mod set_pay_schedule;
+pub mod set_employee_pay_schedule;
+pub mod issue_advance;
+pub mod write_off_advance;
+pub mod create_withholding_vault;
+pub mod close_withholding_vault;
+pub mod set_withholding_lines;
+pub mod remit_withholding;
+pub mod set_budget_commitment;
+pub mod verify_budget_compliance;
+pub mod register_verifying_key;
+pub mod update_verifying_key;
+pub mod close_verifying_key;
+pub mod configure_confidential_treasury;
+pub mod shield_treasury;
+pub mod unshield_treasury;
+pub mod process_confidential_payment;
 
 pub use initialize_company::*;
 pub use add_employee::*;
@@ -11,3 +61,53 @@ pub use update_employee_salary::*;
 pub use remove_employee::*;
 pub use process_payment::*;
 pub use record_payment_proof::*;
+pub use fund_treasury::*;
+pub use queue_withdrawal::*;
+pub use execute_withdrawal::*;
+pub use cancel_withdrawal::*;
+pub use set_withdrawal_delay::*;
+pub use add_treasury_mint::*;
+pub use set_payroll_obligation::*;
+pub use get_runway::*;
+pub use set_spending_cap::*;
+pub use initialize_protocol_config::*;
+pub use update_protocol_fee::*;
+pub use initialize_fee_vault::*;
+pub use sweep_fees::*;
+pub use close_treasury::*;
+pub use close_company::*;
+pub use set_guardian::*;
+pub use pause_protocol::*;
+pub use unpause_protocol::*;
+pub use pause_company::*;
+pub use unpause_company::*;
+pub use open_payment_correction::*;
+pub use accept_payment_correction::*;
+pub use cancel_payment_correction::*;
+pub use process_payroll_batch::*;
+pub use open_payroll_run::*;
+pub use resume_payroll_run::*;
+pub use finalize_payroll_run::*;
+pub use approve_payroll_plan::*;
+pub use crank_payroll_plan::*;
+pub use revoke_payroll_plan::*;
+pub use set_stream_rate::*;
+pub use set_stream_paused::*;
+pub use withdraw_accrued::*;
+pub use set_pay_schedule::*;
+pub use set_employee_pay_schedule::*;
+pub use issue_advance::*;
+pub use write_off_advance::*;
+pub use create_withholding_vault::*;
+pub use close_withholding_vault::*;
+pub use set_withholding_lines::*;
+pub use remit_withholding::*;
+pub use set_budget_commitment::*;
+pub use verify_budget_compliance::*;
+pub use register_verifying_key::*;
+pub use update_verifying_key::*;
+pub use close_verifying_key::*;
+pub use configure_confidential_treasury::*;
+pub use shield_treasury::*;
+pub use unshield_treasury::*;
+pub use process_confidential_payment::*;
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+
+use crate::commitment::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct OpenPaymentCorrection<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = employee.bump,
+        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound
+    )]
+    pub employee: Account<'info, Employee>,
+
+    /// Record of the payment being corrected; a payment can be corrected
+    /// again until everything paid has been returned
+    #[account(
+        seeds = [
+            PAYMENT_PROOF_SEED,
+            company.key().as_ref(),
+            employee.wallet.as_ref(),
+            &payment_proof.payment_id.to_le_bytes()
+        ],
+        bump = payment_proof.bump,
+        constraint = matches!(
+            payment_proof.status,
+            PaymentStatus::Completed | PaymentStatus::Corrected
+        ) @ GhostPayrollError::InvalidPaymentProof
+    )]
+    pub payment_proof: Account<'info, PaymentProof>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PaymentCorrection::LEN,
+        seeds = [PAYMENT_CORRECTION_SEED, payment_proof.key().as_ref()],
+        bump
+    )]
+    pub payment_correction: Account<'info, PaymentCorrection>,
+
+    /// Company authority (must sign and pay)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Corrections are anchored on the payment's `PaymentProof`, so only payments
+/// with a recorded proof can be corrected. One correction is open per payment
+/// at a time; accepting or cancelling it closes the account for the next.
+pub(crate) fn handler(
+    ctx: Context<OpenPaymentCorrection>,
+    amount: u64,
+    paid_amount: u64,
+    paid_amount_nonce: [u8; 32],
+) -> Result<()> {
+    require!(amount > 0, GhostPayrollError::InvalidCorrectionAmount);
+
+    let employee = &ctx.accounts.employee;
+    let payment_proof = &ctx.accounts.payment_proof;
+
+    // The record only holds a commitment, so the company opens it to prove
+    // what was paid; an employee is never asked to return more than that
+    verify_amount_commitment(&payment_proof.amount_commitment, paid_amount, &paid_amount_nonce)?;
+    let returnable = paid_amount
+        .checked_sub(payment_proof.clawed_back_amount)
+        .ok_or(GhostPayrollError::InvalidCorrectionAmount)?;
+    require!(amount <= returnable, GhostPayrollError::InvalidCorrectionAmount);
+    let payment_correction = &mut ctx.accounts.payment_correction;
+    let clock = Clock::get()?;
+
+    payment_correction.company = ctx.accounts.company.key();
+    payment_correction.employee = employee.wallet;
+    payment_correction.payment_proof = payment_proof.key();
+    payment_correction.payment_id = payment_proof.payment_id;
+    payment_correction.mint = employee.payout_mint;
+    payment_correction.amount = amount;
+    payment_correction.opened_at = clock.unix_timestamp;
+    payment_correction.completed_at = 0;
+    payment_correction.status = CorrectionStatus::Pending;
+    payment_correction.bump = ctx.bumps.payment_correction;
+
+    emit!(PaymentCorrectionOpened {
+        company: payment_correction.company,
+        employee: payment_correction.employee,
+        payment_id: payment_correction.payment_id,
+        mint: payment_correction.mint,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Payment correction opened");
+    msg!("Payment ID: {}", payment_correction.payment_id);
+    msg!("Employee: {}", payment_correction.employee);
+    msg!("Amount to return: {}", amount);
+
+    Ok(())
+}
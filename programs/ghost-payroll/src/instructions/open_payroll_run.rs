@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(period: u64)]
+pub struct OpenPayrollRun<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused,
+        constraint = !company.has_open_payroll_run @ GhostPayrollError::PayrollRunAlreadyOpen,
+        constraint = company.payroll_run_count == period @ GhostPayrollError::InvalidPayrollPeriod
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PayrollRun::LEN,
+        seeds = [PAYROLL_RUN_SEED, company.key().as_ref(), &period.to_le_bytes()],
+        bump
+    )]
+    pub payroll_run: Box<Account<'info, PayrollRun>>,
+
+    /// Company authority (must sign and pay)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<OpenPayrollRun>, period: u64) -> Result<()> {
+    let company = &mut ctx.accounts.company;
+    let payroll_run = &mut ctx.accounts.payroll_run;
+    let clock = Clock::get()?;
+
+    payroll_run.company = company.key();
+    payroll_run.period = period;
+    payroll_run.status = PayrollRunStatus::Open;
+    payroll_run.opened_at = clock.unix_timestamp;
+    payroll_run.finalized_at = 0;
+    payroll_run.resume_count = 0;
+    payroll_run.payments_made = 0;
+    payroll_run.total_paid = 0;
+    payroll_run.paid_bitmap = [0; PAYROLL_RUN_BITMAP_BYTES];
+    payroll_run.bump = ctx.bumps.payroll_run;
+
+    company.has_open_payroll_run = true;
+    company.payroll_run_count = company
+        .payroll_run_count
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    emit!(PayrollRunOpened {
+        company: company.key(),
+        period,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Payroll run opened for company: {}", company.name);
+    msg!("Period: {}", period);
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct PauseCompany<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = !company.paused @ GhostPayrollError::AlreadyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<PauseCompany>, reason_code: u16) -> Result<()> {
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+
+    company.paused = true;
+    company.pause_reason = reason_code;
+    company.paused_at = clock.unix_timestamp;
+
+    emit!(CompanyPaused {
+        company: company.key(),
+        authority: company.authority,
+        reason_code,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Company paused: {}", company.name);
+    msg!("Reason code: {}", reason_code);
+
+    Ok(())
+}
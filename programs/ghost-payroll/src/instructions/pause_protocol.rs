@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct PauseProtocol<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.guardian == guardian.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = !protocol_config.paused @ GhostPayrollError::AlreadyPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Protocol guardian (must sign)
+    pub guardian: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<PauseProtocol>, reason_code: u16) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    let clock = Clock::get()?;
+
+    protocol_config.paused = true;
+    protocol_config.pause_reason = reason_code;
+    protocol_config.paused_at = clock.unix_timestamp;
+
+    emit!(ProtocolPaused {
+        guardian: protocol_config.guardian,
+        reason_code,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Program paused by guardian");
+    msg!("Reason code: {}", reason_code);
+
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::zk_token_proof_program;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::commitment::*;
+use crate::confidential::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::payroll::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ProcessConfidentialPayment<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = employee.bump,
+        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound,
+        constraint = employee.is_active || employee.final_payment_pending @ GhostPayrollError::EmployeeInactive
+    )]
+    pub employee: Account<'info, Employee>,
+
+    /// Company treasury configured for confidential transfers
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, company.key().as_ref(), employee.payout_mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Employee's confidential token account for receiving payment
+    #[account(
+        mut,
+        constraint = employee_token_account.owner == employee.wallet @ GhostPayrollError::InvalidTokenMint,
+        constraint = employee_token_account.mint == employee.payout_mint @ GhostPayrollError::InvalidTokenMint
+    )]
+    pub employee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint the employee is paid in
+    #[account(
+        address = employee.payout_mint @ GhostPayrollError::InvalidTokenMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Open payroll run to record this payment against (required for salary while a run is open)
+    #[account(
+        mut,
+        seeds = [PAYROLL_RUN_SEED, company.key().as_ref(), &payroll_run.period.to_le_bytes()],
+        bump = payroll_run.bump,
+        constraint = payroll_run.status == PayrollRunStatus::Open @ GhostPayrollError::PayrollRunNotOpen
+    )]
+    pub payroll_run: Option<Box<Account<'info, PayrollRun>>>,
+
+    /// Context state of a `VerifyCiphertextCommitmentEquality` proof over the treasury's new balance
+    /// CHECK: Owner is the proof program, contents are validated by the token program
+    #[account(owner = zk_token_proof_program::id() @ GhostPayrollError::InvalidTransferProof)]
+    pub equality_proof: UncheckedAccount<'info>,
+
+    /// Context state of a `VerifyBatchedGroupedCiphertext2HandlesValidity` proof over the amount
+    /// CHECK: Owner is the proof program, contents are validated by the token program
+    #[account(owner = zk_token_proof_program::id() @ GhostPayrollError::InvalidTransferProof)]
+    pub ciphertext_validity_proof: UncheckedAccount<'info>,
+
+    /// Context state of a `VerifyBatchedRangeProofU128` proof over the new balance and amount
+    /// CHECK: Owner is the proof program, contents are validated by the token program
+    #[account(owner = zk_token_proof_program::id() @ GhostPayrollError::InvalidTransferProof)]
+    pub range_proof: UncheckedAccount<'info>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Pays an employee from the confidential treasury. The amount only exists
+/// as ciphertexts in the transfer proofs: salary is bound to the employee's
+/// salary commitment through `salary_opening_difference` (the transfer
+/// amount's combined opening minus the salary's), and the spending cap and
+/// protocol fee were charged when the funds were shielded. Withholdings,
+/// advance repayments and prorated periods split or scale the amount, so
+/// they are refused rather than skipped.
+pub(crate) fn handler(
+    ctx: Context<ProcessConfidentialPayment>,
+    new_source_decryptable_available_balance: [u8; 36],
+    source_decrypt_handles: [u8; 64],
+    salary_opening_difference: Option<[u8; 32]>,
+    category: PaymentCategory,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+    let accounts = ctx.accounts;
+    let off_cycle = category.is_off_cycle();
+
+    require!(
+        !(category.is_withheld() && !accounts.employee.withholding_lines.is_empty()),
+        GhostPayrollError::ConfidentialPayoutUnsupported
+    );
+
+    // Salary must pay exactly the committed full-period amount
+    let proration = if off_cycle {
+        None
+    } else {
+        let employee = &accounts.employee;
+        check_on_cycle_payment(
+            &accounts.company,
+            employee,
+            accounts.payroll_run.is_some(),
+            current_time,
+        )?;
+
+        let basis = employee.proration_basis();
+        require!(
+            !basis.is_partial() && !employee.has_advance,
+            GhostPayrollError::ConfidentialPayoutUnsupported
+        );
+        let opening_difference =
+            salary_opening_difference.ok_or(GhostPayrollError::SalaryCommitmentMismatch)?;
+        verify_transfer_amount(
+            &accounts.ciphertext_validity_proof,
+            &employee.salary_commitment,
+            &opening_difference,
+        )?;
+        Some(basis)
+    };
+
+    let treasury = ConfidentialTreasury {
+        company: &accounts.company,
+        treasury: &accounts.treasury,
+        mint: &accounts.mint,
+        token_program: accounts.token_program.to_account_info(),
+    };
+    treasury.transfer(
+        accounts.employee_token_account.to_account_info(),
+        TransferProofs {
+            equality: accounts.equality_proof.to_account_info(),
+            ciphertext_validity: accounts.ciphertext_validity_proof.to_account_info(),
+            range: accounts.range_proof.to_account_info(),
+        },
+        new_source_decryptable_available_balance,
+        source_decrypt_handles,
+    )?;
+
+    let company = &mut accounts.company;
+    let employee = &mut accounts.employee;
+
+    // A payroll run pays each employee at most once; the amount stays encrypted
+    if let Some(payroll_run) = accounts.payroll_run.as_deref_mut() {
+        payroll_run.record_payment(employee.employee_index, 0)?;
+    }
+
+    // Only the payment is counted; the amount stays out of the per-category
+    // totals, and there is no commitment for payment proofs to cover
+    record_payment(company, employee, category, None, current_time)?;
+    employee.confidential_payment_count = employee
+        .confidential_payment_count
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    let final_payment = match proration {
+        Some(basis) => settle_pay_period(company, employee, basis)?,
+        None => false,
+    };
+
+    emit!(ConfidentialPaymentProcessed {
+        company: company.key(),
+        employee: employee.wallet,
+        mint: employee.payout_mint,
+        category,
+        payment_number: employee.total_payments_received,
+        next_payment_due: employee.next_payment_due,
+        final_payment,
+        timestamp: current_time,
+    });
+
+    msg!("Confidential payment processed");
+    msg!("Employee: {}", employee.wallet);
+    msg!("Payment #{}", employee.total_payments_received);
+    msg!("Category: {:?}", category);
+    if !off_cycle {
+        msg!("Next payment due: {}", employee.next_payment_due);
+    }
+
+    Ok(())
+}
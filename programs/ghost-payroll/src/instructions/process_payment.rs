@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+use crate::commitment::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::payroll::*;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -12,7 +14,8 @@ pub struct ProcessPayment<'info> {
         seeds = [COMPANY_SEED, authority.key().as_ref()],
         bump = company.bump,
         constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
-        constraint = company.is_active @ GhostPayrollError::CompanyInactive
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
     )]
     pub company: Account<'info, Company>,
 
@@ -21,94 +24,166 @@ pub struct ProcessPayment<'info> {
         seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
         bump = employee.bump,
         constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound,
-        constraint = employee.is_active @ GhostPayrollError::EmployeeInactive
+        constraint = employee.is_active || employee.final_payment_pending @ GhostPayrollError::EmployeeInactive
     )]
     pub employee: Account<'info, Employee>,
 
-    /// Company treasury token account
+    /// Company treasury token account for the employee's payout mint
     #[account(
         mut,
-        seeds = [TREASURY_SEED, company.key().as_ref()],
-        bump,
-        constraint = treasury.mint == company.payment_token @ GhostPayrollError::InvalidTokenMint
+        seeds = [TREASURY_SEED, company.key().as_ref(), employee.payout_mint.as_ref()],
+        bump
     )]
-    pub treasury: Account<'info, TokenAccount>,
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Accounting record for the employee's payout treasury
+    #[account(
+        mut,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), employee.payout_mint.as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Box<Account<'info, TreasuryLedger>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Protocol fee vault for the payout mint
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, employee.payout_mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
 
     /// Employee's token account for receiving payment
     #[account(
         mut,
         constraint = employee_token_account.owner == employee.wallet @ GhostPayrollError::InvalidTokenMint,
-        constraint = employee_token_account.mint == company.payment_token @ GhostPayrollError::InvalidTokenMint
+        constraint = employee_token_account.mint == employee.payout_mint @ GhostPayrollError::InvalidTokenMint
     )]
-    pub employee_token_account: Account<'info, TokenAccount>,
+    pub employee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint the employee is paid in
+    #[account(
+        address = employee.payout_mint @ GhostPayrollError::InvalidTokenMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Open payroll run to record this payment against (required for salary while a run is open)
+    #[account(
+        mut,
+        seeds = [PAYROLL_RUN_SEED, company.key().as_ref(), &payroll_run.period.to_le_bytes()],
+        bump = payroll_run.bump,
+        constraint = payroll_run.status == PayrollRunStatus::Open @ GhostPayrollError::PayrollRunNotOpen
+    )]
+    pub payroll_run: Option<Box<Account<'info, PayrollRun>>>,
+
+    /// Outstanding advance, required while the employee is repaying one (optional)
+    #[account(
+        mut,
+        seeds = [ADVANCE_SEED, employee.key().as_ref()],
+        bump = advance.bump
+    )]
+    pub advance: Option<Box<Account<'info, Advance>>>,
 
     /// Company authority (must sign)
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(
-    ctx: Context<ProcessPayment>,
+/// Remaining accounts are the employee's withholding vaults, one per
+/// withholding line and in the same order (only for withheld categories).
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ProcessPayment<'info>>,
     amount: u64,
+    full_period_amount: u64,
     amount_commitment: [u8; 32],
+    amount_nonce: [u8; 32],
+    salary_nonce: Option<[u8; 32]>,
+    category: PaymentCategory,
 ) -> Result<()> {
-    let company = &mut ctx.accounts.company;
-    let employee = &mut ctx.accounts.employee;
+    verify_amount_commitment(&amount_commitment, amount, &amount_nonce)?;
+    verify_salary_payment(
+        &ctx.accounts.employee.salary_commitment,
+        category,
+        full_period_amount,
+        salary_nonce,
+    )?;
+
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
 
-    // Validate amount
-    require!(amount >= MIN_SALARY_AMOUNT, GhostPayrollError::InvalidSalaryAmount);
-
-    // Check if company treasury has sufficient balance
-    require!(
-        ctx.accounts.treasury.amount >= amount,
-        GhostPayrollError::InsufficientCompanyBalance
-    );
-
-    // Create PDA signer seeds for company
-    let authority_key = ctx.accounts.authority.key();
-    let company_seeds = &[
-        COMPANY_SEED,
-        authority_key.as_ref(),
-        &[company.bump],
-    ];
-    let signer_seeds = &[&company_seeds[..]];
-
-    // Transfer tokens from treasury to employee
-    let transfer_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.treasury.to_account_info(),
-            to: ctx.accounts.employee_token_account.to_account_info(),
-            authority: company.to_account_info(),
+    let withholding_vaults = ctx.remaining_accounts;
+    let accounts = ctx.accounts;
+    let mut payroll = PayrollAccounts {
+        company: &mut accounts.company,
+        treasury: &mut accounts.treasury,
+        treasury_ledger: &mut accounts.treasury_ledger,
+        protocol_config: &accounts.protocol_config,
+        fee_vault: &accounts.fee_vault,
+        mint: &accounts.mint,
+        token_program: &accounts.token_program,
+        payroll_run: accounts.payroll_run.as_deref_mut().map(|run| &mut **run),
+    };
+
+    let payout = pay_employee(
+        &mut payroll,
+        &mut accounts.employee,
+        accounts.advance.as_deref_mut().map(|advance| &mut **advance),
+        accounts.employee_token_account.to_account_info(),
+        withholding_vaults,
+        PaymentRequest {
+            amount,
+            full_period_amount,
+            category,
+            amount_commitment: Some(amount_commitment),
         },
-        signer_seeds,
-    );
-
-    token::transfer(transfer_ctx, amount)?;
-
-    // Update employee payment records
-    employee.last_payment_date = current_time;
-    employee.total_payments_received = employee
-        .total_payments_received
-        .checked_add(1)
-        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
-
-    // Update company payment records
-    company.last_payment_timestamp = current_time;
-    company.next_payment_due = current_time + employee.payment_frequency.seconds_to_next_payment();
-    company.total_payments_made = company
-        .total_payments_made
-        .checked_add(1)
-        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+        current_time,
+    )?;
+
+    // A repaid advance is closed and its rent returned to the authority
+    if payout.advance_repaid {
+        if let Some(advance) = &accounts.advance {
+            advance.close(accounts.authority.to_account_info())?;
+        }
+    }
+
+    let employee = &accounts.employee;
 
     msg!("Payment processed successfully");
     msg!("Employee: {}", employee.wallet);
-    msg!("Amount commitment: {:?}", amount_commitment);
+    msg!("Amount commitment verified: {:?}", amount_commitment);
+    if payout.fee_withheld > 0 {
+        msg!("Transfer fee covered by treasury: {}", payout.fee_withheld);
+    }
+    msg!("Protocol fee: {}", payout.protocol_fee);
+    if payout.withheld > 0 {
+        msg!("Withheld: {}", payout.withheld);
+    }
+    if payout.advance_repayment > 0 {
+        msg!("Advance repayment withheld: {}", payout.advance_repayment);
+    }
     msg!("Payment #{}", employee.total_payments_received);
+    msg!("Category: {:?}", category);
+    if category.is_off_cycle() {
+        msg!("Off-cycle payment");
+    } else {
+        if employee.last_proration.is_partial() {
+            msg!(
+                "Prorated: {} of {} seconds",
+                employee.last_proration.worked_seconds,
+                employee.last_proration.period_end - employee.last_proration.period_start
+            );
+        }
+        msg!("Next payment due: {}", employee.next_payment_due);
+    }
 
     Ok(())
 }
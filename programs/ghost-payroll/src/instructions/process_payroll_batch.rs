@@ -0,0 +1,288 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::commitment::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::payroll::*;
+use crate::state::*;
+
+/// A single payment in a payroll batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct BatchPaymentEntry {
+    /// Payment amount
+    pub amount: u64,
+
+    /// Full-period amount the payment is prorated from
+    pub full_period_amount: u64,
+
+    /// Commitment hash of the payment amount
+    pub amount_commitment: [u8; 32],
+
+    /// Blinding nonce opening `amount_commitment`
+    pub amount_nonce: [u8; 32],
+
+    /// Nonce opening the employee's salary commitment (on-cycle payments only)
+    pub salary_nonce: Option<[u8; 32]>,
+
+    /// What the payment is for; non-salary categories are paid off-cycle
+    pub category: PaymentCategory,
+}
+
+/// Per-entry outcome returned by `process_payroll_batch`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct BatchPaymentResult {
+    /// Employee wallet paid
+    pub employee: Pubkey,
+
+    /// Amount received by the employee
+    pub amount: u64,
+
+    /// Protocol fee charged for the payment
+    pub protocol_fee: u64,
+
+    /// Whether the payment settled final wages
+    pub final_payment: bool,
+
+    /// Employee's next regular payment date after this payment
+    pub next_payment_due: i64,
+}
+
+/// Pays up to `MAX_BATCH_SIZE` employees from one treasury.
+///
+/// Remaining accounts are `(employee, employee_token_account)` pairs, one
+/// pair per entry and in the same order; both accounts must be writable.
+/// An employee repaying an advance is followed by their writable `Advance`,
+/// then (for withheld categories) their withholding vaults in line order.
+#[derive(Accounts)]
+pub struct ProcessPayrollBatch<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    /// Company treasury token account for the batch's payout mint
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, company.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Accounting record for the payout treasury
+    #[account(
+        mut,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), mint.key().as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Box<Account<'info, TreasuryLedger>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Protocol fee vault for the payout mint
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint every employee in the batch is paid in
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Open payroll run to record this payment against (required for salary while a run is open)
+    #[account(
+        mut,
+        seeds = [PAYROLL_RUN_SEED, company.key().as_ref(), &payroll_run.period.to_le_bytes()],
+        bump = payroll_run.bump,
+        constraint = payroll_run.status == PayrollRunStatus::Open @ GhostPayrollError::PayrollRunNotOpen
+    )]
+    pub payroll_run: Option<Box<Account<'info, PayrollRun>>>,
+
+    /// Company authority (must sign)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ProcessPayrollBatch<'info>>,
+    entries: Vec<BatchPaymentEntry>,
+) -> Result<Vec<BatchPaymentResult>> {
+    require!(
+        !entries.is_empty()
+            && entries.len() <= MAX_BATCH_SIZE
+            && ctx.remaining_accounts.len() >= entries.len() * 2,
+        GhostPayrollError::InvalidBatchSize
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    let program_id = ctx.program_id;
+    let remaining_accounts = ctx.remaining_accounts;
+    let accounts = ctx.accounts;
+    let company_key = accounts.company.key();
+    let payout_mint = accounts.mint.key();
+    let authority_info = accounts.authority.to_account_info();
+
+    let mut payroll = PayrollAccounts {
+        company: &mut accounts.company,
+        treasury: &mut accounts.treasury,
+        treasury_ledger: &mut accounts.treasury_ledger,
+        protocol_config: &accounts.protocol_config,
+        fee_vault: &accounts.fee_vault,
+        mint: &accounts.mint,
+        token_program: &accounts.token_program,
+        payroll_run: accounts.payroll_run.as_deref_mut().map(|run| &mut **run),
+    };
+
+    let mut paid: Vec<Pubkey> = Vec::with_capacity(entries.len());
+    let mut results = Vec::with_capacity(entries.len());
+
+    let mut remaining = remaining_accounts.iter();
+    for entry in entries.iter() {
+        verify_amount_commitment(&entry.amount_commitment, entry.amount, &entry.amount_nonce)?;
+
+        let employee_info = remaining.next().ok_or(GhostPayrollError::InvalidBatchSize)?;
+        let token_account_info = remaining.next().ok_or(GhostPayrollError::InvalidBatchSize)?;
+
+        // Validate the employee account exactly as ProcessPayment does
+        require!(employee_info.is_writable, ErrorCode::AccountNotMutable);
+        let mut employee = Account::<Employee>::try_from(employee_info)?;
+        let employee_pda = Pubkey::create_program_address(
+            &[
+                EMPLOYEE_SEED,
+                company_key.as_ref(),
+                employee.wallet.as_ref(),
+                &[employee.bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| GhostPayrollError::EmployeeNotFound)?;
+        require_keys_eq!(employee_pda, employee_info.key(), GhostPayrollError::EmployeeNotFound);
+        require_keys_eq!(employee.company, company_key, GhostPayrollError::EmployeeNotFound);
+        require!(
+            employee.is_active || employee.final_payment_pending,
+            GhostPayrollError::EmployeeInactive
+        );
+        require_keys_eq!(employee.payout_mint, payout_mint, GhostPayrollError::InvalidTokenMint);
+        require!(
+            !paid.contains(employee_info.key),
+            GhostPayrollError::PaymentAlreadyProcessed
+        );
+
+        verify_salary_payment(
+            &employee.salary_commitment,
+            entry.category,
+            entry.full_period_amount,
+            entry.salary_nonce,
+        )?;
+
+        // Validate the employee's token account
+        require!(token_account_info.is_writable, ErrorCode::AccountNotMutable);
+        let employee_token_account = InterfaceAccount::<TokenAccount>::try_from(token_account_info)?;
+        require_keys_eq!(
+            employee_token_account.owner,
+            employee.wallet,
+            GhostPayrollError::InvalidTokenMint
+        );
+        require_keys_eq!(
+            employee_token_account.mint,
+            employee.payout_mint,
+            GhostPayrollError::InvalidTokenMint
+        );
+
+        // Validate the employee's advance, if they are repaying one
+        let mut advance = if employee.has_advance {
+            let advance_info = remaining
+                .next()
+                .ok_or(GhostPayrollError::AdvanceAccountRequired)?;
+            require!(advance_info.is_writable, ErrorCode::AccountNotMutable);
+            let advance = Account::<Advance>::try_from(advance_info)?;
+            let advance_pda = Pubkey::create_program_address(
+                &[ADVANCE_SEED, employee_info.key.as_ref(), &[advance.bump]],
+                program_id,
+            )
+            .map_err(|_| GhostPayrollError::AdvanceAccountRequired)?;
+            require_keys_eq!(
+                advance_pda,
+                advance_info.key(),
+                GhostPayrollError::AdvanceAccountRequired
+            );
+            Some(advance)
+        } else {
+            None
+        };
+
+        // Take the employee's withholding vaults; pay_employee checks them against the lines
+        let vault_count = if entry.category.is_withheld() {
+            employee.withholding_lines.len()
+        } else {
+            0
+        };
+        let withholding_vaults = remaining
+            .by_ref()
+            .take(vault_count)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let payout = pay_employee(
+            &mut payroll,
+            &mut employee,
+            advance.as_deref_mut(),
+            token_account_info.clone(),
+            &withholding_vaults,
+            PaymentRequest {
+                amount: entry.amount,
+                full_period_amount: entry.full_period_amount,
+                category: entry.category,
+                amount_commitment: Some(entry.amount_commitment),
+            },
+            current_time,
+        )?;
+
+        // Persist the updated employee record
+        employee.exit(program_id)?;
+
+        // Persist the advance, or close it once repaid
+        if let Some(advance) = advance {
+            if payout.advance_repaid {
+                advance.close(authority_info.clone())?;
+            } else {
+                advance.exit(program_id)?;
+            }
+        }
+
+        msg!("Paid employee: {}", employee.wallet);
+        msg!("Amount commitment verified: {:?}", entry.amount_commitment);
+
+        paid.push(employee_info.key());
+        results.push(BatchPaymentResult {
+            employee: employee.wallet,
+            amount: entry.amount - payout.withheld - payout.advance_repayment,
+            protocol_fee: payout.protocol_fee,
+            final_payment: payout.final_payment,
+            next_payment_due: employee.next_payment_due,
+        });
+    }
+
+    require!(remaining.next().is_none(), GhostPayrollError::InvalidBatchSize);
+
+    msg!("Payroll batch processed successfully");
+    msg!("Payments: {}", results.len());
+
+    Ok(results)
+}
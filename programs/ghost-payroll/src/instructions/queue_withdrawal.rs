@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct QueueWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TreasuryWithdrawal::LEN,
+        seeds = [
+            WITHDRAWAL_SEED,
+            company.key().as_ref(),
+            &company.withdrawal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub withdrawal: Account<'info, TreasuryWithdrawal>,
+
+    /// Company treasury token account for the withdrawn mint
+    #[account(
+        seeds = [TREASURY_SEED, company.key().as_ref(), destination.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token account that will receive the withdrawn funds
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Company authority (must sign and pay)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<QueueWithdrawal>, amount: u64) -> Result<()> {
+    require!(amount > 0, GhostPayrollError::InvalidWithdrawalAmount);
+
+    let company = &mut ctx.accounts.company;
+    let withdrawal = &mut ctx.accounts.withdrawal;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Initialize withdrawal request
+    withdrawal.company = company.key();
+    withdrawal.withdrawal_id = company.withdrawal_count;
+    withdrawal.mint = ctx.accounts.treasury.mint;
+    withdrawal.source = ctx.accounts.treasury.key();
+    withdrawal.amount = amount;
+    withdrawal.destination = ctx.accounts.destination.key();
+    withdrawal.requested_at = current_time;
+    withdrawal.executable_at = current_time
+        .checked_add(company.withdrawal_delay)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    withdrawal.status = WithdrawalStatus::Pending;
+    withdrawal.bump = ctx.bumps.withdrawal;
+
+    company.withdrawal_count = company
+        .withdrawal_count
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    company.pending_withdrawals = company
+        .pending_withdrawals
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    emit!(WithdrawalQueued {
+        company: company.key(),
+        withdrawal_id: withdrawal.withdrawal_id,
+        mint: withdrawal.mint,
+        amount,
+        destination: withdrawal.destination,
+        executable_at: withdrawal.executable_at,
+        timestamp: current_time,
+    });
+
+    msg!("Treasury withdrawal queued for company: {}", company.name);
+    msg!("Withdrawal ID: {}", withdrawal.withdrawal_id);
+    msg!("Mint: {}", withdrawal.mint);
+    msg!("Amount: {}", amount);
+    msg!("Executable at: {}", withdrawal.executable_at);
+
+    Ok(())
+}
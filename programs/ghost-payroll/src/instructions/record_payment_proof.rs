@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::*;
+use crate::groth16::*;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -11,10 +12,19 @@ pub struct RecordPaymentProof<'info> {
         seeds = [COMPANY_SEED, authority.key().as_ref()],
         bump = company.bump,
         constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
-        constraint = company.is_active @ GhostPayrollError::CompanyInactive
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
     )]
     pub company: Account<'info, Company>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Employee whose latest payment the proof covers
     #[account(
         seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
         bump = employee.bump,
@@ -22,6 +32,13 @@ pub struct RecordPaymentProof<'info> {
     )]
     pub employee: Account<'info, Employee>,
 
+    /// Company's Groth16 verifying key for payment proofs
+    #[account(
+        seeds = [VERIFYING_KEY_SEED, company.key().as_ref()],
+        bump = verifying_key.bump
+    )]
+    pub verifying_key: Box<Account<'info, PaymentVerifyingKey>>,
+
     #[account(
         init,
         payer = authority,
@@ -43,19 +60,29 @@ pub struct RecordPaymentProof<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<RecordPaymentProof>,
     payment_id: u64,
     amount_commitment: [u8; 32],
     zk_proof: Vec<u8>,
     shadowwire_tx_signature: String,
 ) -> Result<()> {
-    // Validate ZK proof size
+    // Proofs cover the employee's latest payment: its payment number and the
+    // amount commitment it was made with (its category is copied from it)
+    let employee = &ctx.accounts.employee;
     require!(
-        zk_proof.len() <= PaymentProof::MAX_ZK_PROOF_SIZE,
-        GhostPayrollError::InvalidPaymentProof
+        payment_id == employee.total_payments_received
+            && employee.last_payment_commitment != [0; 32]
+            && amount_commitment == employee.last_payment_commitment,
+        GhostPayrollError::PaymentNotRecorded
     );
 
+    // The proof must attest that the committed amount was paid to this employee
+    let proof = Groth16Proof::from_bytes(&zk_proof)?;
+    let public_inputs =
+        payment_public_inputs(&amount_commitment, &ctx.accounts.employee.wallet, payment_id);
+    verify_groth16(&ctx.accounts.verifying_key, &proof, &public_inputs)?;
+
     // Validate signature length
     require!(
         shadowwire_tx_signature.len() <= PaymentProof::MAX_SIGNATURE_LENGTH,
@@ -74,12 +101,16 @@ pub fn handler(
     payment_proof.zk_proof = zk_proof;
     payment_proof.shadowwire_tx_signature = shadowwire_tx_signature;
     payment_proof.status = PaymentStatus::Completed;
+    payment_proof.category = ctx.accounts.employee.last_payment_category;
+    payment_proof.clawed_back_amount = 0;
     payment_proof.bump = ctx.bumps.payment_proof;
 
     msg!("Payment proof recorded");
     msg!("Payment ID: {}", payment_proof.payment_id);
     msg!("Employee: {}", payment_proof.employee);
     msg!("Company: {}", payment_proof.company);
+    msg!("Category: {:?}", payment_proof.category);
+    msg!("ZK proof verified");
     msg!("ShadowWire TX: {}", payment_proof.shadowwire_tx_signature);
 
     Ok(())
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::groth16::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RegisterVerifyingKey<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PaymentVerifyingKey::LEN,
+        seeds = [VERIFYING_KEY_SEED, company.key().as_ref()],
+        bump
+    )]
+    pub verifying_key: Box<Account<'info, PaymentVerifyingKey>>,
+
+    /// Company authority (must sign and pay)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<RegisterVerifyingKey>,
+    alpha_g1: [u8; 64],
+    beta_g2: [u8; 128],
+    gamma_g2: [u8; 128],
+    delta_g2: [u8; 128],
+    ic: [[u8; 64]; PAYMENT_PROOF_PUBLIC_INPUTS + 1],
+) -> Result<()> {
+    // Reject keys whose points are not on the curve
+    validate_verifying_key(&alpha_g1, &beta_g2, &gamma_g2, &delta_g2, &ic)?;
+
+    let verifying_key = &mut ctx.accounts.verifying_key;
+    let clock = Clock::get()?;
+
+    verifying_key.company = ctx.accounts.company.key();
+    verifying_key.alpha_g1 = alpha_g1;
+    verifying_key.beta_g2 = beta_g2;
+    verifying_key.gamma_g2 = gamma_g2;
+    verifying_key.delta_g2 = delta_g2;
+    verifying_key.ic = ic;
+    verifying_key.registered_at = clock.unix_timestamp;
+    verifying_key.bump = ctx.bumps.verifying_key;
+
+    emit!(VerifyingKeyRegistered {
+        company: verifying_key.company,
+        verifying_key: verifying_key.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Payment verifying key registered");
+    msg!("Company: {}", verifying_key.company);
+
+    Ok(())
+}
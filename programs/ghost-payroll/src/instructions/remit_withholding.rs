@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct RemitWithholding<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TreasuryWithdrawal::LEN,
+        seeds = [
+            WITHDRAWAL_SEED,
+            company.key().as_ref(),
+            &company.withdrawal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub withdrawal: Account<'info, TreasuryWithdrawal>,
+
+    /// Token mint withholdings are held in
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Withholding vault to remit from
+    #[account(
+        seeds = [WITHHOLDING_VAULT_SEED, company.key().as_ref(), mint.key().as_ref(), name.as_bytes()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Tax authority or benefits provider receiving the remittance
+    #[account(
+        constraint = destination.mint == mint.key() @ GhostPayrollError::InvalidTokenMint
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Company authority (must sign and pay)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Queues a remittance as a treasury withdrawal drawn from the vault. Funds
+/// leave company control, so they wait out the same timelock and count
+/// against the same spending cap when executed.
+pub(crate) fn handler(ctx: Context<RemitWithholding>, name: String, amount: u64) -> Result<()> {
+    require!(amount > 0, GhostPayrollError::InvalidWithdrawalAmount);
+    require!(
+        ctx.accounts.vault.amount >= amount,
+        GhostPayrollError::InsufficientCompanyBalance
+    );
+
+    let company = &mut ctx.accounts.company;
+    let withdrawal = &mut ctx.accounts.withdrawal;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    withdrawal.company = company.key();
+    withdrawal.withdrawal_id = company.withdrawal_count;
+    withdrawal.mint = ctx.accounts.mint.key();
+    withdrawal.source = ctx.accounts.vault.key();
+    withdrawal.amount = amount;
+    withdrawal.destination = ctx.accounts.destination.key();
+    withdrawal.requested_at = current_time;
+    withdrawal.executable_at = current_time
+        .checked_add(company.withdrawal_delay)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    withdrawal.status = WithdrawalStatus::Pending;
+    withdrawal.bump = ctx.bumps.withdrawal;
+
+    company.withdrawal_count = company
+        .withdrawal_count
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    company.pending_withdrawals = company
+        .pending_withdrawals
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    emit!(WithholdingRemittanceQueued {
+        company: company.key(),
+        withdrawal_id: withdrawal.withdrawal_id,
+        vault: withdrawal.source,
+        name: name.clone(),
+        destination: withdrawal.destination,
+        amount,
+        executable_at: withdrawal.executable_at,
+        timestamp: current_time,
+    });
+
+    msg!("Withholding remittance queued: {}", name);
+    msg!("Withdrawal ID: {}", withdrawal.withdrawal_id);
+    msg!("Amount: {}", amount);
+    msg!("Destination: {}", withdrawal.destination);
+    msg!("Executable at: {}", withdrawal.executable_at);
+
+    Ok(())
+}
@@ -11,15 +11,24 @@ pub struct RemoveEmployee<'info> {
         seeds = [COMPANY_SEED, authority.key().as_ref()],
         bump = company.bump,
         constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
-        constraint = company.is_active @ GhostPayrollError::CompanyInactive
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
     )]
     pub company: Account<'info, Company>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     #[account(
         mut,
         seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
         bump = employee.bump,
-        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound
+        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound,
+        constraint = employee.is_active @ GhostPayrollError::EmployeeInactive
     )]
     pub employee: Account<'info, Employee>,
 
@@ -27,12 +36,43 @@ pub struct RemoveEmployee<'info> {
     pub authority: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<RemoveEmployee>) -> Result<()> {
+pub(crate) fn handler(ctx: Context<RemoveEmployee>) -> Result<()> {
     let company = &mut ctx.accounts.company;
     let employee = &mut ctx.accounts.employee;
+    let clock = Clock::get()?;
+
+    // Settle streamed wages up to termination and stop the stream; the
+    // accrued balance stays withdrawable
+    employee.settle_stream(clock.unix_timestamp)?;
+    let streamed = employee.stream_rate > 0;
+    employee.stream_rate = 0;
 
     // Mark employee as inactive (soft delete)
     employee.is_active = false;
+    employee.termination_date = clock.unix_timestamp;
+
+    // Time worked since the last paid period is owed as final wages, and
+    // blocks the company from shutting down until paid. A streamed
+    // employee's wages are the accrued balance instead.
+    let final_payment_due = !streamed && employee.proration_basis().worked_seconds > 0;
+    if final_payment_due {
+        employee.final_payment_pending = true;
+        company.pending_final_payments = company
+            .pending_final_payments
+            .checked_add(1)
+            .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    }
+
+    // Unwithdrawn streamed wages block shutdown the same way
+    if employee.stream_accrued > 0 {
+        company.pending_stream_withdrawals = company
+            .pending_stream_withdrawals
+            .checked_add(1)
+            .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    }
+
+    // Former employees no longer count against the budget
+    company.remove_salary_commitment(&employee.salary_commitment)?;
 
     // Decrement company employee count
     company.employee_count = company
@@ -42,6 +82,10 @@ pub fn handler(ctx: Context<RemoveEmployee>) -> Result<()> {
 
     msg!("Employee removed from company: {}", company.name);
     msg!("Employee wallet: {}", employee.wallet);
+    msg!("Final payment due: {}", final_payment_due);
+    if employee.stream_accrued > 0 {
+        msg!("Streamed wages awaiting withdrawal: {}", employee.stream_accrued);
+    }
     msg!("Remaining employees: {}", company.employee_count);
 
     Ok(())
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ResumePayrollRun<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [PAYROLL_RUN_SEED, company.key().as_ref(), &payroll_run.period.to_le_bytes()],
+        bump = payroll_run.bump,
+        constraint = payroll_run.status == PayrollRunStatus::Open @ GhostPayrollError::PayrollRunNotOpen
+    )]
+    pub payroll_run: Box<Account<'info, PayrollRun>>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+/// Returns the number of employees already paid in the run
+pub(crate) fn handler(ctx: Context<ResumePayrollRun>) -> Result<u16> {
+    let payroll_run = &mut ctx.accounts.payroll_run;
+    let clock = Clock::get()?;
+
+    payroll_run.resume_count = payroll_run
+        .resume_count
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    emit!(PayrollRunResumed {
+        company: payroll_run.company,
+        period: payroll_run.period,
+        payments_made: payroll_run.payments_made,
+        resume_count: payroll_run.resume_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Payroll run resumed");
+    msg!("Period: {}", payroll_run.period);
+    msg!("Employees already paid: {}", payroll_run.payments_made);
+
+    Ok(payroll_run.payments_made)
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RevokePayrollPlan<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [PAYROLL_PLAN_SEED, company.key().as_ref(), payroll_plan.employee.as_ref()],
+        bump = payroll_plan.bump,
+        constraint = payroll_plan.company == company.key() @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub payroll_plan: Account<'info, PayrollPlan>,
+
+    /// Company authority (must sign, receives rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<RevokePayrollPlan>) -> Result<()> {
+    let payroll_plan = &ctx.accounts.payroll_plan;
+    let clock = Clock::get()?;
+
+    emit!(PayrollPlanRevoked {
+        company: payroll_plan.company,
+        employee: payroll_plan.employee,
+        periods_paid: payroll_plan.periods_paid,
+        periods_approved: payroll_plan.periods_approved,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Payroll plan revoked");
+    msg!("Employee: {}", payroll_plan.employee);
+    msg!(
+        "Periods paid: {}/{}",
+        payroll_plan.periods_paid,
+        payroll_plan.periods_approved
+    );
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::commitment::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetBudgetCommitment<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetBudgetCommitment>, budget_commitment: [u8; 32]) -> Result<()> {
+    validate_pedersen_commitment(&budget_commitment)?;
+
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+
+    // A new budget needs a fresh range proof
+    company.budget_commitment = budget_commitment;
+    company.budget_verified_at = 0;
+
+    emit!(BudgetCommitmentUpdated {
+        company: company.key(),
+        budget_commitment,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Budget commitment updated");
+    msg!("Company: {}", company.name);
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetEmployeePaySchedule<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = employee.bump,
+        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound,
+        constraint = employee.is_active @ GhostPayrollError::EmployeeInactive
+    )]
+    pub employee: Account<'info, Employee>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetEmployeePaySchedule>,
+    payment_frequency: PaymentFrequency,
+    pay_anchor: i64,
+) -> Result<()> {
+    let employee = &mut ctx.accounts.employee;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(
+        pay_anchor > 0 && pay_anchor <= current_time + MAX_PAY_ANCHOR_LEAD,
+        GhostPayrollError::InvalidPayAnchor
+    );
+
+    // The first payday on the new calendar follows the end of the last paid
+    // period, so overdue periods stay due and the first new period is
+    // prorated from where the old calendar left off
+    employee.payment_frequency = payment_frequency;
+    employee.pay_anchor = pay_anchor;
+    employee.next_payment_due =
+        payment_frequency.next_payment_after(pay_anchor, employee.paid_through);
+
+    emit!(PayScheduleUpdated {
+        company: employee.company,
+        employee: Some(employee.wallet),
+        payment_frequency,
+        pay_anchor,
+        next_payment_due: employee.next_payment_due,
+        timestamp: current_time,
+    });
+
+    msg!("Employee pay schedule updated: {:?}", payment_frequency);
+    msg!("Employee wallet: {}", employee.wallet);
+    msg!("Next payment due: {}", employee.next_payment_due);
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.admin == admin.key() @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Protocol admin (must sign)
+    pub admin: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetGuardian>, new_guardian: Pubkey) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    let clock = Clock::get()?;
+    let old_guardian = protocol_config.guardian;
+
+    protocol_config.guardian = new_guardian;
+
+    emit!(GuardianUpdated {
+        admin: protocol_config.admin,
+        old_guardian,
+        new_guardian,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Guardian updated: {} -> {}", old_guardian, new_guardian);
+
+    Ok(())
+}
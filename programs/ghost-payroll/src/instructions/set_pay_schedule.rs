@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetPaySchedule<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetPaySchedule>,
+    payment_frequency: PaymentFrequency,
+    pay_anchor: i64,
+) -> Result<()> {
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(
+        pay_anchor > 0 && pay_anchor <= current_time + MAX_PAY_ANCHOR_LEAD,
+        GhostPayrollError::InvalidPayAnchor
+    );
+
+    company.payment_frequency = payment_frequency;
+    company.pay_anchor = pay_anchor;
+    company.next_payment_due = payment_frequency.next_payment_after(pay_anchor, current_time);
+
+    emit!(PayScheduleUpdated {
+        company: company.key(),
+        employee: None,
+        payment_frequency,
+        pay_anchor,
+        next_payment_due: company.next_payment_due,
+        timestamp: current_time,
+    });
+
+    msg!("Company pay schedule updated: {:?}", payment_frequency);
+    msg!("Anchor: {}", pay_anchor);
+    msg!("Next payment due: {}", company.next_payment_due);
+
+    Ok(())
+}
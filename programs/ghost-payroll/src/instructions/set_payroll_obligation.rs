@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetPayrollObligation<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), treasury_ledger.mint.as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Account<'info, TreasuryLedger>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<SetPayrollObligation>,
+    period_obligation: u64,
+    low_runway_threshold: u16,
+) -> Result<()> {
+    let treasury_ledger = &mut ctx.accounts.treasury_ledger;
+    let clock = Clock::get()?;
+
+    treasury_ledger.period_obligation = period_obligation;
+    treasury_ledger.low_runway_threshold = low_runway_threshold;
+
+    emit!(PayrollObligationUpdated {
+        company: ctx.accounts.company.key(),
+        mint: treasury_ledger.mint,
+        period_obligation,
+        low_runway_threshold,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Payroll obligation updated");
+    msg!("Mint: {}", treasury_ledger.mint);
+    msg!("Obligation per period: {}", period_obligation);
+    msg!("Low runway threshold: {} periods", low_runway_threshold);
+
+    Ok(())
+}
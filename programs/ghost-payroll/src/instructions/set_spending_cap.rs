@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetSpendingCap<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), treasury_ledger.mint.as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Account<'info, TreasuryLedger>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+/// Sets the rolling spending cap for a treasury (0 = unlimited).
+/// Lowering the cap applies immediately; raising it waits out the same
+/// timelock as treasury withdrawals so a compromised key cannot lift it at once.
+pub(crate) fn handler(ctx: Context<SetSpendingCap>, new_cap: u64) -> Result<()> {
+    let company = &ctx.accounts.company;
+    let treasury_ledger = &mut ctx.accounts.treasury_ledger;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    treasury_ledger.apply_pending_spending_cap(current_time);
+
+    let current_cap = treasury_ledger.spending_cap;
+    let is_increase = current_cap != 0 && (new_cap == 0 || new_cap > current_cap);
+
+    if is_increase {
+        let effective_at = current_time
+            .checked_add(company.withdrawal_delay)
+            .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+        treasury_ledger.pending_spending_cap = new_cap;
+        treasury_ledger.pending_cap_effective_at = effective_at;
+
+        emit!(SpendingCapIncreaseQueued {
+            company: company.key(),
+            mint: treasury_ledger.mint,
+            current_cap,
+            pending_cap: new_cap,
+            effective_at,
+            timestamp: current_time,
+        });
+
+        msg!("Spending cap increase queued");
+        msg!("Current cap: {}, pending cap: {}", current_cap, new_cap);
+        msg!("Effective at: {}", effective_at);
+    } else {
+        // A decrease also discards any pending increase
+        treasury_ledger.spending_cap = new_cap;
+        treasury_ledger.pending_spending_cap = 0;
+        treasury_ledger.pending_cap_effective_at = 0;
+
+        emit!(SpendingCapUpdated {
+            company: company.key(),
+            mint: treasury_ledger.mint,
+            spending_cap: new_cap,
+            timestamp: current_time,
+        });
+
+        msg!("Spending cap updated");
+        msg!("New cap: {}", new_cap);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetStreamPaused<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = employee.bump,
+        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound,
+        constraint = employee.is_active @ GhostPayrollError::EmployeeInactive
+    )]
+    pub employee: Account<'info, Employee>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetStreamPaused>, paused: bool) -> Result<()> {
+    let employee = &mut ctx.accounts.employee;
+    let clock = Clock::get()?;
+
+    require!(
+        employee.stream_paused != paused,
+        if paused {
+            GhostPayrollError::AlreadyPaused
+        } else {
+            GhostPayrollError::NotPaused
+        }
+    );
+
+    // Settle up to now so the paused interval never accrues
+    employee.settle_stream(clock.unix_timestamp)?;
+    employee.stream_paused = paused;
+
+    emit!(StreamPauseUpdated {
+        company: employee.company,
+        employee: employee.wallet,
+        paused,
+        accrued: employee.stream_accrued,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Stream {}", if paused { "paused" } else { "resumed" });
+    msg!("Employee wallet: {}", employee.wallet);
+
+    Ok(())
+}
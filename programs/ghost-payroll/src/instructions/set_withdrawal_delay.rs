@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetWithdrawalDelay<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetWithdrawalDelay>, new_delay: i64) -> Result<()> {
+    // The floor keeps every withdrawal visible for at least a day,
+    // even if the authority key is compromised
+    require!(
+        (MIN_WITHDRAWAL_DELAY..=MAX_WITHDRAWAL_DELAY).contains(&new_delay),
+        GhostPayrollError::InvalidWithdrawalDelay
+    );
+
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+    let old_delay = company.withdrawal_delay;
+
+    // Only affects withdrawals queued from now on
+    company.withdrawal_delay = new_delay;
+
+    emit!(WithdrawalDelayUpdated {
+        company: company.key(),
+        old_delay,
+        new_delay,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Withdrawal delay updated for company: {}", company.name);
+    msg!("Old delay: {}s, new delay: {}s", old_delay, new_delay);
+
+    Ok(())
+}
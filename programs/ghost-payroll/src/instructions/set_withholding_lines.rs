@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+/// A withholding line as configured by the company
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct WithholdingLineConfig {
+    /// Name of the withholding vault the line pays into
+    pub name: String,
+
+    /// How much of each payment is withheld
+    pub rate: WithholdingRate,
+}
+
+#[derive(Accounts)]
+pub struct SetWithholdingLines<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = employee.bump,
+        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound,
+        constraint = employee.is_active @ GhostPayrollError::EmployeeInactive
+    )]
+    pub employee: Account<'info, Employee>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetWithholdingLines>, lines: Vec<WithholdingLineConfig>) -> Result<()> {
+    require!(
+        lines.len() <= MAX_WITHHOLDING_LINES,
+        GhostPayrollError::InvalidWithholdingLine
+    );
+
+    let company_key = ctx.accounts.company.key();
+    let employee = &mut ctx.accounts.employee;
+    let clock = Clock::get()?;
+
+    // Basis-point lines together must leave part of every payment to the employee
+    let mut total_bps: u64 = 0;
+    let mut new_lines: Vec<WithholdingLine> = Vec::with_capacity(lines.len());
+    for line in lines {
+        require!(
+            !line.name.is_empty() && line.name.len() <= MAX_WITHHOLDING_NAME_LENGTH,
+            GhostPayrollError::InvalidWithholdingLine
+        );
+        require!(
+            !new_lines.iter().any(|existing| existing.name == line.name),
+            GhostPayrollError::InvalidWithholdingLine
+        );
+        match line.rate {
+            WithholdingRate::BasisPoints(bps) => {
+                require!(bps > 0, GhostPayrollError::InvalidWithholdingLine);
+                total_bps += bps as u64;
+            }
+            WithholdingRate::Fixed(amount) => {
+                require!(amount > 0, GhostPayrollError::InvalidWithholdingLine);
+            }
+        }
+
+        let (vault, _) = Pubkey::find_program_address(
+            &[
+                WITHHOLDING_VAULT_SEED,
+                company_key.as_ref(),
+                employee.payout_mint.as_ref(),
+                line.name.as_bytes(),
+            ],
+            ctx.program_id,
+        );
+
+        // Running totals carry over for lines that keep their vault
+        let total_withheld = employee
+            .withholding_lines
+            .iter()
+            .find(|existing| existing.vault == vault)
+            .map_or(0, |existing| existing.total_withheld);
+
+        new_lines.push(WithholdingLine {
+            name: line.name,
+            vault,
+            rate: line.rate,
+            total_withheld,
+        });
+    }
+    require!(
+        total_bps < BPS_DENOMINATOR,
+        GhostPayrollError::InvalidWithholdingLine
+    );
+
+    employee.withholding_lines = new_lines;
+
+    emit!(WithholdingLinesUpdated {
+        company: company_key,
+        employee: employee.wallet,
+        lines: employee.withholding_lines.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Withholding lines updated");
+    msg!("Employee: {}", employee.wallet);
+    msg!("Lines: {}", employee.withholding_lines.len());
+
+    Ok(())
+}
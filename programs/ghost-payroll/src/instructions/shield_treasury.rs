@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::confidential::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+use crate::transfers::*;
+
+#[derive(Accounts)]
+pub struct ShieldTreasury<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Company treasury configured for confidential transfers
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, company.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Accounting record for the treasury
+    #[account(
+        mut,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), mint.key().as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Box<Account<'info, TreasuryLedger>>,
+
+    /// Protocol fee vault for the treasury mint
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint of the treasury
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+
+    /// Token-2022; the confidential instruction builders refuse any other program
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Shielded tokens count as spent: payouts from the encrypted balance hide
+/// their amounts, so the spending cap and the protocol fee are charged on the
+/// way in. Neither is refunded if the tokens are unshielded again.
+pub(crate) fn handler(
+    ctx: Context<ShieldTreasury>,
+    amount: u64,
+    expected_pending_balance_credit_counter: u64,
+    new_decryptable_available_balance: [u8; 36],
+) -> Result<()> {
+    let accounts = ctx.accounts;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(amount > 0, GhostPayrollError::InvalidDepositAmount);
+
+    // Protocol fee is charged to the company on top of the shielded amount
+    let protocol_fee = accounts.protocol_config.fee_for(amount)?;
+    let total_outflow = amount
+        .checked_add(protocol_fee)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    require!(
+        accounts.treasury.amount >= total_outflow,
+        GhostPayrollError::InsufficientCompanyBalance
+    );
+
+    // Enforce the rolling spending cap
+    accounts
+        .treasury_ledger
+        .record_outflow(total_outflow, current_time)?;
+
+    // Route the protocol fee into the fee vault
+    if protocol_fee > 0 {
+        transfer_from_treasury(
+            &accounts.token_program,
+            &accounts.treasury,
+            &accounts.mint,
+            accounts.fee_vault.to_account_info(),
+            &accounts.company,
+            protocol_fee,
+        )?;
+
+        // The payees are only known, encrypted, at payout time
+        emit!(ProtocolFeeCollected {
+            company: accounts.company.key(),
+            employee: Pubkey::default(),
+            mint: accounts.mint.key(),
+            payment_amount: amount,
+            fee_amount: protocol_fee,
+            fee_bps: accounts.protocol_config.fee_bps,
+            timestamp: current_time,
+        });
+    }
+
+    let treasury = ConfidentialTreasury {
+        company: &accounts.company,
+        treasury: &accounts.treasury,
+        mint: &accounts.mint,
+        token_program: accounts.token_program.to_account_info(),
+    };
+    treasury.shield(
+        amount,
+        expected_pending_balance_credit_counter,
+        new_decryptable_available_balance,
+    )?;
+
+    emit!(TreasuryShielded {
+        company: accounts.company.key(),
+        mint: accounts.mint.key(),
+        amount,
+        protocol_fee,
+        timestamp: current_time,
+    });
+
+    msg!("Treasury balance shielded: {}", amount);
+    msg!("Mint: {}", accounts.mint.key());
+    msg!("Protocol fee: {}", protocol_fee);
+
+    Ok(())
+}
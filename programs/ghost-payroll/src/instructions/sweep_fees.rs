@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SweepFees<'info> {
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.admin == admin.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Fee vault for the mint being swept
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint of the fee vault
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token account receiving the swept fees
+    #[account(
+        mut,
+        constraint = destination.mint == mint.key() @ GhostPayrollError::InvalidTokenMint
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// Protocol admin (must sign)
+    pub admin: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub(crate) fn handler(ctx: Context<SweepFees>) -> Result<()> {
+    let amount = ctx.accounts.fee_vault.amount;
+    require!(amount > 0, GhostPayrollError::InvalidWithdrawalAmount);
+
+    let protocol_config_seeds = &[PROTOCOL_CONFIG_SEED, &[ctx.accounts.protocol_config.bump]];
+    let signer_seeds = &[&protocol_config_seeds[..]];
+
+    // Transfer the full vault balance to the destination
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.fee_vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.protocol_config.to_account_info(),
+        },
+        signer_seeds,
+    );
+
+    token_interface::transfer_checked(transfer_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    let clock = Clock::get()?;
+
+    emit!(ProtocolFeesSwept {
+        mint: ctx.accounts.mint.key(),
+        amount,
+        destination: ctx.accounts.destination.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Protocol fees swept");
+    msg!("Mint: {}", ctx.accounts.mint.key());
+    msg!("Amount: {}", amount);
+
+    Ok(())
+}
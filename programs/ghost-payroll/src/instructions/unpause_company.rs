@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct UnpauseCompany<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.paused @ GhostPayrollError::NotPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<UnpauseCompany>) -> Result<()> {
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+
+    emit!(CompanyUnpaused {
+        company: company.key(),
+        authority: company.authority,
+        reason_code: company.pause_reason,
+        paused_at: company.paused_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    company.paused = false;
+    company.pause_reason = 0;
+    company.paused_at = 0;
+
+    msg!("Company unpaused: {}", company.name);
+
+    Ok(())
+}
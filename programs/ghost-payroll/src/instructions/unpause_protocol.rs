@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct UnpauseProtocol<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.guardian == guardian.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = protocol_config.paused @ GhostPayrollError::NotPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Protocol guardian (must sign)
+    pub guardian: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<UnpauseProtocol>) -> Result<()> {
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    let clock = Clock::get()?;
+
+    emit!(ProtocolUnpaused {
+        guardian: protocol_config.guardian,
+        reason_code: protocol_config.pause_reason,
+        paused_at: protocol_config.paused_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    protocol_config.paused = false;
+    protocol_config.pause_reason = 0;
+    protocol_config.paused_at = 0;
+
+    msg!("Program unpaused by guardian");
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::zk_token_proof_program;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::confidential::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct UnshieldTreasury<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Company treasury configured for confidential transfers
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, company.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint of the treasury
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Context state of a `VerifyWithdraw` proof over the treasury's encrypted balance
+    /// CHECK: Owner is the proof program, contents are validated by the token program
+    #[account(owner = zk_token_proof_program::id() @ GhostPayrollError::InvalidTransferProof)]
+    pub withdraw_proof: UncheckedAccount<'info>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Tokens return to the public balance, where withdrawals and close_treasury
+/// can reach them. The spending cap and protocol fee charged when they were
+/// shielded are not refunded.
+pub(crate) fn handler(
+    ctx: Context<UnshieldTreasury>,
+    amount: u64,
+    new_decryptable_available_balance: [u8; 36],
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, GhostPayrollError::InvalidWithdrawalAmount);
+
+    let treasury = ConfidentialTreasury {
+        company: &accounts.company,
+        treasury: &accounts.treasury,
+        mint: &accounts.mint,
+        token_program: accounts.token_program.to_account_info(),
+    };
+    treasury.unshield(
+        amount,
+        new_decryptable_available_balance,
+        accounts.withdraw_proof.to_account_info(),
+    )?;
+
+    emit!(TreasuryUnshielded {
+        company: accounts.company.key(),
+        mint: accounts.mint.key(),
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Treasury balance unshielded: {}", amount);
+    msg!("Mint: {}", accounts.mint.key());
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::zk_token_proof_program;
 
+use crate::commitment::*;
 use crate::constants::*;
 use crate::errors::*;
 use crate::state::*;
@@ -7,13 +9,22 @@ use crate::state::*;
 #[derive(Accounts)]
 pub struct UpdateEmployeeSalary<'info> {
     #[account(
+        mut,
         seeds = [COMPANY_SEED, authority.key().as_ref()],
         bump = company.bump,
         constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
-        constraint = company.is_active @ GhostPayrollError::CompanyInactive
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
     )]
     pub company: Account<'info, Company>,
 
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
     #[account(
         mut,
         seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
@@ -23,11 +34,16 @@ pub struct UpdateEmployeeSalary<'info> {
     )]
     pub employee: Account<'info, Employee>,
 
+    /// Context state of a `VerifyRangeProofU64` proof over the salary commitment
+    /// CHECK: Owner is the proof program, contents are validated in the handler
+    #[account(owner = zk_token_proof_program::id() @ GhostPayrollError::InvalidRangeProof)]
+    pub salary_range_proof: UncheckedAccount<'info>,
+
     /// Company authority (must sign)
     pub authority: Signer<'info>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<UpdateEmployeeSalary>,
     new_encrypted_salary: Vec<u8>,
     new_salary_commitment: [u8; 32],
@@ -38,9 +54,17 @@ pub fn handler(
             && new_encrypted_salary.len() <= Employee::MAX_ENCRYPTED_SALARY_SIZE,
         GhostPayrollError::InvalidEncryptedSalary
     );
+    validate_pedersen_commitment(&new_salary_commitment)?;
+    // A commitment to a negative or wrapped salary would understate the budget total
+    verify_range_proof(&ctx.accounts.salary_range_proof, &new_salary_commitment)?;
 
+    let company = &mut ctx.accounts.company;
     let employee = &mut ctx.accounts.employee;
 
+    // Swap the old salary for the new one in the company's committed total
+    company.remove_salary_commitment(&employee.salary_commitment)?;
+    company.add_salary_commitment(&new_salary_commitment)?;
+
     // Update salary data
     employee.encrypted_salary = new_encrypted_salary;
     employee.salary_commitment = new_salary_commitment;
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct UpdateProtocolFee<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.admin == admin.key() @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Protocol admin (must sign)
+    pub admin: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateProtocolFee>, new_fee_bps: u16) -> Result<()> {
+    require!(
+        new_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+        GhostPayrollError::InvalidProtocolFee
+    );
+
+    let protocol_config = &mut ctx.accounts.protocol_config;
+    let clock = Clock::get()?;
+    let old_fee_bps = protocol_config.fee_bps;
+
+    protocol_config.fee_bps = new_fee_bps;
+
+    emit!(ProtocolFeeUpdated {
+        admin: protocol_config.admin,
+        old_fee_bps,
+        new_fee_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Protocol fee updated: {} bps -> {} bps", old_fee_bps, new_fee_bps);
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::groth16::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct UpdateVerifyingKey<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [VERIFYING_KEY_SEED, company.key().as_ref()],
+        bump = verifying_key.bump,
+        constraint = verifying_key.company == company.key() @ GhostPayrollError::UnauthorizedAccess
+    )]
+    pub verifying_key: Box<Account<'info, PaymentVerifyingKey>>,
+
+    /// Company authority (must sign)
+    pub authority: Signer<'info>,
+}
+
+/// Proofs already recorded stay valid; later proofs are checked against the new key
+pub(crate) fn handler(
+    ctx: Context<UpdateVerifyingKey>,
+    alpha_g1: [u8; 64],
+    beta_g2: [u8; 128],
+    gamma_g2: [u8; 128],
+    delta_g2: [u8; 128],
+    ic: [[u8; 64]; PAYMENT_PROOF_PUBLIC_INPUTS + 1],
+) -> Result<()> {
+    // Reject keys whose points are not on the curve
+    validate_verifying_key(&alpha_g1, &beta_g2, &gamma_g2, &delta_g2, &ic)?;
+
+    let verifying_key = &mut ctx.accounts.verifying_key;
+    let clock = Clock::get()?;
+
+    verifying_key.alpha_g1 = alpha_g1;
+    verifying_key.beta_g2 = beta_g2;
+    verifying_key.gamma_g2 = gamma_g2;
+    verifying_key.delta_g2 = delta_g2;
+    verifying_key.ic = ic;
+    verifying_key.registered_at = clock.unix_timestamp;
+
+    emit!(VerifyingKeyUpdated {
+        company: verifying_key.company,
+        verifying_key: verifying_key.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Payment verifying key updated");
+    msg!("Company: {}", verifying_key.company);
+
+    Ok(())
+}
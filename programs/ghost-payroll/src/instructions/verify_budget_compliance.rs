@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::zk_token_proof_program;
+
+use crate::commitment::*;
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+/// Permissionless: anyone holding a verified range proof over the company's
+/// budget headroom can record that payroll fits the budget.
+#[derive(Accounts)]
+pub struct VerifyBudgetCompliance<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.authority.as_ref()],
+        bump = company.bump,
+        constraint = company.is_active @ GhostPayrollError::CompanyInactive,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Context state of a `VerifyRangeProofU64` proof, written by the ZK Token proof program
+    /// CHECK: Owner is the proof program, contents are validated in the handler
+    #[account(owner = zk_token_proof_program::id() @ GhostPayrollError::InvalidRangeProof)]
+    pub range_proof_context: UncheckedAccount<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<VerifyBudgetCompliance>) -> Result<()> {
+    let company = &mut ctx.accounts.company;
+    let clock = Clock::get()?;
+
+    // budget - salaries commits to a 64-bit value only if salaries fit the budget
+    let headroom = subtract_commitments(&company.budget_commitment, &company.salary_commitment_total)?;
+    verify_range_proof(&ctx.accounts.range_proof_context, &headroom)?;
+
+    company.budget_verified_at = clock.unix_timestamp;
+
+    emit!(BudgetComplianceVerified {
+        company: company.key(),
+        budget_commitment: company.budget_commitment,
+        salary_commitment_total: company.salary_commitment_total,
+        active_employees: company.employee_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Budget compliance verified");
+    msg!("Company: {}", company.name);
+    msg!("Active employees: {}", company.employee_count);
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::payroll::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct WithdrawAccrued<'info> {
+    #[account(
+        mut,
+        seeds = [COMPANY_SEED, company.authority.as_ref()],
+        bump = company.bump,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee_wallet.key().as_ref()],
+        bump = employee.bump,
+        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound
+    )]
+    pub employee: Account<'info, Employee>,
+
+    /// Company treasury token account for the employee's payout mint
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED, company.key().as_ref(), employee.payout_mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// Accounting record for the employee's payout treasury
+    #[account(
+        mut,
+        seeds = [TREASURY_LEDGER_SEED, company.key().as_ref(), employee.payout_mint.as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Box<Account<'info, TreasuryLedger>>,
+
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.paused @ GhostPayrollError::ProtocolPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Protocol fee vault for the payout mint
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, employee.payout_mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Employee's token account for receiving the withdrawal
+    #[account(
+        mut,
+        constraint = employee_token_account.owner == employee_wallet.key() @ GhostPayrollError::InvalidTokenMint,
+        constraint = employee_token_account.mint == employee.payout_mint @ GhostPayrollError::InvalidTokenMint
+    )]
+    pub employee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint the employee is paid in
+    #[account(
+        address = employee.payout_mint @ GhostPayrollError::InvalidTokenMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Employee wallet (must sign)
+    pub employee_wallet: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub(crate) fn handler(ctx: Context<WithdrawAccrued>) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    let accounts = ctx.accounts;
+    let employee = &mut accounts.employee;
+
+    employee.settle_stream(current_time)?;
+    let amount = employee.stream_accrued;
+    require!(amount > 0, GhostPayrollError::NothingAccrued);
+
+    let mut payroll = PayrollAccounts {
+        company: &mut accounts.company,
+        treasury: &mut accounts.treasury,
+        treasury_ledger: &mut accounts.treasury_ledger,
+        protocol_config: &accounts.protocol_config,
+        fee_vault: &accounts.fee_vault,
+        mint: &accounts.mint,
+        token_program: &accounts.token_program,
+        payroll_run: None,
+    };
+
+    let disbursement = disburse(
+        &mut payroll,
+        employee.wallet,
+        accounts.employee_token_account.to_account_info(),
+        amount,
+        current_time,
+    )?;
+
+    employee.stream_accrued = 0;
+    employee.last_payment_date = current_time;
+
+    // A removed employee's last withdrawal releases the company for shutdown
+    if !employee.is_active {
+        let company = &mut accounts.company;
+        company.pending_stream_withdrawals = company
+            .pending_stream_withdrawals
+            .checked_sub(1)
+            .ok_or(GhostPayrollError::ArithmeticUnderflow)?;
+    }
+
+    employee.total_streamed = employee
+        .total_streamed
+        .checked_add(amount)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    emit!(AccruedWithdrawn {
+        company: employee.company,
+        employee: employee.wallet,
+        mint: employee.payout_mint,
+        amount,
+        total_streamed: employee.total_streamed,
+        timestamp: current_time,
+    });
+
+    msg!("Streamed wages withdrawn");
+    msg!("Employee: {}", employee.wallet);
+    msg!("Amount: {}", amount);
+    msg!("Protocol fee: {}", disbursement.protocol_fee);
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct WriteOffAdvance<'info> {
+    #[account(
+        seeds = [COMPANY_SEED, authority.key().as_ref()],
+        bump = company.bump,
+        constraint = company.authority == authority.key() @ GhostPayrollError::UnauthorizedAccess,
+        constraint = !company.paused @ GhostPayrollError::CompanyPaused
+    )]
+    pub company: Account<'info, Company>,
+
+    #[account(
+        mut,
+        seeds = [EMPLOYEE_SEED, company.key().as_ref(), employee.wallet.as_ref()],
+        bump = employee.bump,
+        constraint = employee.company == company.key() @ GhostPayrollError::EmployeeNotFound
+    )]
+    pub employee: Account<'info, Employee>,
+
+    /// Outstanding advance being written off (rent returned to the authority)
+    #[account(
+        mut,
+        close = authority,
+        seeds = [ADVANCE_SEED, employee.key().as_ref()],
+        bump = advance.bump
+    )]
+    pub advance: Box<Account<'info, Advance>>,
+
+    /// Company authority (must sign, receives rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Forgives whatever is left of an advance, typically once a removed
+/// employee's final salary could not cover it. Later salary payments are no
+/// longer withheld from.
+pub(crate) fn handler(ctx: Context<WriteOffAdvance>) -> Result<()> {
+    let clock = Clock::get()?;
+    let advance = &ctx.accounts.advance;
+    let employee = &mut ctx.accounts.employee;
+
+    employee.has_advance = false;
+
+    emit!(AdvanceWrittenOff {
+        company: advance.company,
+        employee: advance.employee,
+        mint: advance.mint,
+        principal: advance.principal,
+        unrecovered: advance.outstanding,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Advance written off");
+    msg!("Employee: {}", advance.employee);
+    msg!("Unrecovered: {}", advance.outstanding);
+
+    Ok(())
+}
@@ -1,12 +1,20 @@
 use anchor_lang::prelude::*;
 
+pub mod calendar;
+pub mod commitment;
+pub mod confidential;
 pub mod constants;
 pub mod errors;
+pub mod events;
+pub mod groth16;
 pub mod instructions;
+pub mod payroll;
 pub mod state;
+pub mod transfers;
 
+use constants::PAYMENT_PROOF_PUBLIC_INPUTS;
 use instructions::*;
-use state::PaymentFrequency;
+use state::{PaymentCategory, PaymentFrequency};
 
 declare_id!("BW7Efo8SJQhm5TuAiogpJZPjzNKxc4WQhT8PVhBgpsoa");
 
@@ -14,7 +22,7 @@ declare_id!("BW7Efo8SJQhm5TuAiogpJZPjzNKxc4WQhT8PVhBgpsoa");
 pub mod ghost_payroll {
     use super::*;
 
-    /// Initialize a new company with a treasury account
+    /// Initialize a new company with a treasury account for its default mint
     pub fn initialize_company(
         ctx: Context<InitializeCompany>,
         name: String,
@@ -57,12 +65,28 @@ pub mod ghost_payroll {
 
     /// Process a payment from company treasury to employee
     /// Note: For private payments, this is used in combination with ShadowWire
-    pub fn process_payment(
-        ctx: Context<ProcessPayment>,
+    /// Non-salary categories are paid outside the employee's regular schedule;
+    /// salary and final payments must equal `full_period_amount` prorated for partial periods.
+    /// `amount_nonce` must open `amount_commitment` to `amount`, and for on-cycle payments
+    /// `salary_nonce` must open the employee's salary commitment to `full_period_amount`
+    pub fn process_payment<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProcessPayment<'info>>,
         amount: u64,
+        full_period_amount: u64,
         amount_commitment: [u8; 32],
+        amount_nonce: [u8; 32],
+        salary_nonce: Option<[u8; 32]>,
+        category: PaymentCategory,
     ) -> Result<()> {
-        instructions::process_payment::handler(ctx, amount, amount_commitment)
+        instructions::process_payment::handler(
+            ctx,
+            amount,
+            full_period_amount,
+            amount_commitment,
+            amount_nonce,
+            salary_nonce,
+            category,
+        )
     }
 
     /// Record a payment proof on-chain (for ShadowWire private payments)
@@ -81,4 +105,355 @@ pub mod ghost_payroll {
             shadowwire_tx_signature,
         )
     }
+
+    /// Deposit tokens into the company treasury and record the funding
+    pub fn fund_treasury(ctx: Context<FundTreasury>, amount: u64) -> Result<()> {
+        instructions::fund_treasury::handler(ctx, amount)
+    }
+
+    /// Queue a timelocked withdrawal from the company treasury
+    pub fn queue_withdrawal(ctx: Context<QueueWithdrawal>, amount: u64) -> Result<()> {
+        instructions::queue_withdrawal::handler(ctx, amount)
+    }
+
+    /// Execute a queued treasury withdrawal once its timelock has elapsed
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        instructions::execute_withdrawal::handler(ctx)
+    }
+
+    /// Cancel a pending treasury withdrawal
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        instructions::cancel_withdrawal::handler(ctx)
+    }
+
+    /// Update the timelock applied to newly queued treasury withdrawals
+    pub fn set_withdrawal_delay(ctx: Context<SetWithdrawalDelay>, new_delay: i64) -> Result<()> {
+        instructions::set_withdrawal_delay::handler(ctx, new_delay)
+    }
+
+    /// Open an additional treasury so the company can pay in another mint
+    pub fn add_treasury_mint(ctx: Context<AddTreasuryMint>) -> Result<()> {
+        instructions::add_treasury_mint::handler(ctx)
+    }
+
+    /// Commit the per-period payroll obligation and low-runway threshold for a treasury
+    pub fn set_payroll_obligation(
+        ctx: Context<SetPayrollObligation>,
+        period_obligation: u64,
+        low_runway_threshold: u16,
+    ) -> Result<()> {
+        instructions::set_payroll_obligation::handler(ctx, period_obligation, low_runway_threshold)
+    }
+
+    /// View: number of full pay periods the treasury can cover (via return data)
+    pub fn get_runway(ctx: Context<GetRunway>) -> Result<u64> {
+        instructions::get_runway::handler(ctx)
+    }
+
+    /// Set the rolling 30-day spending cap for a treasury (raises are timelocked)
+    pub fn set_spending_cap(ctx: Context<SetSpendingCap>, new_cap: u64) -> Result<()> {
+        instructions::set_spending_cap::handler(ctx, new_cap)
+    }
+
+    /// Initialize the global protocol config (program upgrade authority only)
+    pub fn initialize_protocol_config(ctx: Context<InitializeProtocolConfig>) -> Result<()> {
+        instructions::initialize_protocol_config::handler(ctx)
+    }
+
+    /// Update the protocol payment fee
+    pub fn update_protocol_fee(ctx: Context<UpdateProtocolFee>, new_fee_bps: u16) -> Result<()> {
+        instructions::update_protocol_fee::handler(ctx, new_fee_bps)
+    }
+
+    /// Open the protocol fee vault for a mint
+    pub fn initialize_fee_vault(ctx: Context<InitializeFeeVault>) -> Result<()> {
+        instructions::initialize_fee_vault::handler(ctx)
+    }
+
+    /// Sweep collected protocol fees out of a fee vault
+    pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+        instructions::sweep_fees::handler(ctx)
+    }
+
+    /// Drain a treasury to the authority and close it, winding the company down
+    pub fn close_treasury(ctx: Context<CloseTreasury>) -> Result<()> {
+        instructions::close_treasury::handler(ctx)
+    }
+
+    /// Close a company once all employees are settled and treasuries closed
+    pub fn close_company(ctx: Context<CloseCompany>) -> Result<()> {
+        instructions::close_company::handler(ctx)
+    }
+
+    /// Hand the pause guardian role to a new key
+    pub fn set_guardian(ctx: Context<SetGuardian>, new_guardian: Pubkey) -> Result<()> {
+        instructions::set_guardian::handler(ctx, new_guardian)
+    }
+
+    /// Emergency-halt every state-changing instruction (guardian only)
+    pub fn pause_protocol(ctx: Context<PauseProtocol>, reason_code: u16) -> Result<()> {
+        instructions::pause_protocol::handler(ctx, reason_code)
+    }
+
+    /// Lift a program-wide pause (guardian only)
+    pub fn unpause_protocol(ctx: Context<UnpauseProtocol>) -> Result<()> {
+        instructions::unpause_protocol::handler(ctx)
+    }
+
+    /// Halt all operations for a single company
+    pub fn pause_company(ctx: Context<PauseCompany>, reason_code: u16) -> Result<()> {
+        instructions::pause_company::handler(ctx, reason_code)
+    }
+
+    /// Lift a company pause
+    pub fn unpause_company(ctx: Context<UnpauseCompany>) -> Result<()> {
+        instructions::unpause_company::handler(ctx)
+    }
+
+    /// Ask an employee to return part of a mistaken payment with a recorded proof;
+    /// `paid_amount_nonce` must open the payment record's commitment to `paid_amount`
+    pub fn open_payment_correction(
+        ctx: Context<OpenPaymentCorrection>,
+        amount: u64,
+        paid_amount: u64,
+        paid_amount_nonce: [u8; 32],
+    ) -> Result<()> {
+        instructions::open_payment_correction::handler(ctx, amount, paid_amount, paid_amount_nonce)
+    }
+
+    /// Employee-signed return of a corrected payment to the treasury
+    pub fn accept_payment_correction(ctx: Context<AcceptPaymentCorrection>) -> Result<()> {
+        instructions::accept_payment_correction::handler(ctx)
+    }
+
+    /// Withdraw a payment correction the employee has not yet accepted
+    pub fn cancel_payment_correction(ctx: Context<CancelPaymentCorrection>) -> Result<()> {
+        instructions::cancel_payment_correction::handler(ctx)
+    }
+
+    /// Pay up to MAX_BATCH_SIZE employees atomically; returns a per-entry summary
+    pub fn process_payroll_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProcessPayrollBatch<'info>>,
+        entries: Vec<BatchPaymentEntry>,
+    ) -> Result<Vec<BatchPaymentResult>> {
+        instructions::process_payroll_batch::handler(ctx, entries)
+    }
+
+    /// Open the payroll run for the company's next pay period
+    pub fn open_payroll_run(ctx: Context<OpenPayrollRun>, period: u64) -> Result<()> {
+        instructions::open_payroll_run::handler(ctx, period)
+    }
+
+    /// Resume an interrupted payroll run; returns how many employees were already paid
+    pub fn resume_payroll_run(ctx: Context<ResumePayrollRun>) -> Result<u16> {
+        instructions::resume_payroll_run::handler(ctx)
+    }
+
+    /// Close out a payroll run so no further payments can be recorded against it
+    pub fn finalize_payroll_run(ctx: Context<FinalizePayrollRun>) -> Result<()> {
+        instructions::finalize_payroll_run::handler(ctx)
+    }
+
+    /// Pre-approve an employee's committed salary for the next `periods` pay periods
+    pub fn approve_payroll_plan(
+        ctx: Context<ApprovePayrollPlan>,
+        amount: u64,
+        amount_commitment: [u8; 32],
+        amount_nonce: [u8; 32],
+        salary_nonce: [u8; 32],
+        periods: u16,
+        keeper_tip: u64,
+    ) -> Result<()> {
+        instructions::approve_payroll_plan::handler(
+            ctx,
+            amount,
+            amount_commitment,
+            amount_nonce,
+            salary_nonce,
+            periods,
+            keeper_tip,
+        )
+    }
+
+    /// Permissionless: pay a due, pre-approved payment and tip the keeper
+    pub fn crank_payroll_plan<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankPayrollPlan<'info>>,
+    ) -> Result<()> {
+        instructions::crank_payroll_plan::handler(ctx)
+    }
+
+    /// Cancel the remaining periods of a payroll plan
+    pub fn revoke_payroll_plan(ctx: Context<RevokePayrollPlan>) -> Result<()> {
+        instructions::revoke_payroll_plan::handler(ctx)
+    }
+
+    /// Set an employee's per-second streaming rate (0 stops the stream)
+    pub fn set_stream_rate(ctx: Context<SetStreamRate>, rate_per_second: u64) -> Result<()> {
+        instructions::set_stream_rate::handler(ctx, rate_per_second)
+    }
+
+    /// Pause or resume accrual of an employee's stream
+    pub fn set_stream_paused(ctx: Context<SetStreamPaused>, paused: bool) -> Result<()> {
+        instructions::set_stream_paused::handler(ctx, paused)
+    }
+
+    /// Employee-signed withdrawal of all streamed wages accrued so far
+    pub fn withdraw_accrued(ctx: Context<WithdrawAccrued>) -> Result<()> {
+        instructions::withdraw_accrued::handler(ctx)
+    }
+
+    /// Set the company pay calendar (frequency and anchor date) used for new hires
+    pub fn set_pay_schedule(
+        ctx: Context<SetPaySchedule>,
+        payment_frequency: PaymentFrequency,
+        pay_anchor: i64,
+    ) -> Result<()> {
+        instructions::set_pay_schedule::handler(ctx, payment_frequency, pay_anchor)
+    }
+
+    /// Move an employee onto a new pay calendar
+    pub fn set_employee_pay_schedule(
+        ctx: Context<SetEmployeePaySchedule>,
+        payment_frequency: PaymentFrequency,
+        pay_anchor: i64,
+    ) -> Result<()> {
+        instructions::set_employee_pay_schedule::handler(ctx, payment_frequency, pay_anchor)
+    }
+
+    /// Advance wages to an employee, repaid by withholding from later salary payments
+    pub fn issue_advance(
+        ctx: Context<IssueAdvance>,
+        amount: u64,
+        repayment_per_period: u64,
+    ) -> Result<()> {
+        instructions::issue_advance::handler(ctx, amount, repayment_per_period)
+    }
+
+    /// Forgive the rest of an employee's advance and close it
+    pub fn write_off_advance(ctx: Context<WriteOffAdvance>) -> Result<()> {
+        instructions::write_off_advance::handler(ctx)
+    }
+
+    /// Open a named company vault that withholdings are routed into
+    pub fn create_withholding_vault(ctx: Context<CreateWithholdingVault>, name: String) -> Result<()> {
+        instructions::create_withholding_vault::handler(ctx, name)
+    }
+
+    /// Close an empty withholding vault and reclaim its rent
+    pub fn close_withholding_vault(ctx: Context<CloseWithholdingVault>, name: String) -> Result<()> {
+        instructions::close_withholding_vault::handler(ctx, name)
+    }
+
+    /// Replace an employee's withholding lines
+    pub fn set_withholding_lines(
+        ctx: Context<SetWithholdingLines>,
+        lines: Vec<WithholdingLineConfig>,
+    ) -> Result<()> {
+        instructions::set_withholding_lines::handler(ctx, lines)
+    }
+
+    /// Queue a timelocked remittance of withheld funds from a withholding vault
+    pub fn remit_withholding(
+        ctx: Context<RemitWithholding>,
+        name: String,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::remit_withholding::handler(ctx, name, amount)
+    }
+
+    /// Replace the company's Pedersen budget commitment
+    pub fn set_budget_commitment(
+        ctx: Context<SetBudgetCommitment>,
+        budget_commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::set_budget_commitment::handler(ctx, budget_commitment)
+    }
+
+    /// Permissionless: record a range proof that active salaries fit the committed budget
+    pub fn verify_budget_compliance(ctx: Context<VerifyBudgetCompliance>) -> Result<()> {
+        instructions::verify_budget_compliance::handler(ctx)
+    }
+
+    /// Register the Groth16 verifying key payment proofs are checked against
+    pub fn register_verifying_key(
+        ctx: Context<RegisterVerifyingKey>,
+        alpha_g1: [u8; 64],
+        beta_g2: [u8; 128],
+        gamma_g2: [u8; 128],
+        delta_g2: [u8; 128],
+        ic: [[u8; 64]; PAYMENT_PROOF_PUBLIC_INPUTS + 1],
+    ) -> Result<()> {
+        instructions::register_verifying_key::handler(ctx, alpha_g1, beta_g2, gamma_g2, delta_g2, ic)
+    }
+
+    /// Replace the company's payment verifying key, e.g. after a circuit upgrade
+    pub fn update_verifying_key(
+        ctx: Context<UpdateVerifyingKey>,
+        alpha_g1: [u8; 64],
+        beta_g2: [u8; 128],
+        gamma_g2: [u8; 128],
+        delta_g2: [u8; 128],
+        ic: [[u8; 64]; PAYMENT_PROOF_PUBLIC_INPUTS + 1],
+    ) -> Result<()> {
+        instructions::update_verifying_key::handler(ctx, alpha_g1, beta_g2, gamma_g2, delta_g2, ic)
+    }
+
+    /// Close the company's payment verifying key and reclaim its rent
+    pub fn close_verifying_key(ctx: Context<CloseVerifyingKey>) -> Result<()> {
+        instructions::close_verifying_key::handler(ctx)
+    }
+
+    /// Enable Token-2022 confidential transfers on a company treasury
+    pub fn configure_confidential_treasury(
+        ctx: Context<ConfigureConfidentialTreasury>,
+        decryptable_zero_balance: [u8; 36],
+        maximum_pending_balance_credit_counter: u64,
+    ) -> Result<()> {
+        instructions::configure_confidential_treasury::handler(
+            ctx,
+            decryptable_zero_balance,
+            maximum_pending_balance_credit_counter,
+        )
+    }
+
+    /// Move public treasury funds into the treasury's encrypted balance
+    pub fn shield_treasury(
+        ctx: Context<ShieldTreasury>,
+        amount: u64,
+        expected_pending_balance_credit_counter: u64,
+        new_decryptable_available_balance: [u8; 36],
+    ) -> Result<()> {
+        instructions::shield_treasury::handler(
+            ctx,
+            amount,
+            expected_pending_balance_credit_counter,
+            new_decryptable_available_balance,
+        )
+    }
+
+    /// Move encrypted treasury funds back into the treasury's public balance
+    pub fn unshield_treasury(
+        ctx: Context<UnshieldTreasury>,
+        amount: u64,
+        new_decryptable_available_balance: [u8; 36],
+    ) -> Result<()> {
+        instructions::unshield_treasury::handler(ctx, amount, new_decryptable_available_balance)
+    }
+
+    /// Pay an employee an encrypted amount from the confidential treasury
+    pub fn process_confidential_payment(
+        ctx: Context<ProcessConfidentialPayment>,
+        new_source_decryptable_available_balance: [u8; 36],
+        source_decrypt_handles: [u8; 64],
+        salary_opening_difference: Option<[u8; 32]>,
+        category: PaymentCategory,
+    ) -> Result<()> {
+        instructions::process_confidential_payment::handler(
+            ctx,
+            new_source_decryptable_available_balance,
+            source_decrypt_handles,
+            salary_opening_difference,
+            category,
+        )
+    }
 }
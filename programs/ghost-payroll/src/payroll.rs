@@ -0,0 +1,484 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+use crate::transfers::*;
+
+/// Company-side accounts shared by every payout from one treasury
+pub struct PayrollAccounts<'a, 'info> {
+    pub company: &'a mut Account<'info, Company>,
+    pub treasury: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub treasury_ledger: &'a mut TreasuryLedger,
+    pub protocol_config: &'a ProtocolConfig,
+    pub fee_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub payroll_run: Option<&'a mut PayrollRun>,
+}
+
+/// What an employee payout is for and how much it pays
+pub struct PaymentRequest {
+    /// Amount owed to the employee before any advance repayment
+    pub amount: u64,
+
+    /// Full-period amount a salary payment is prorated from
+    pub full_period_amount: u64,
+
+    /// What the payment is for
+    pub category: PaymentCategory,
+
+    /// Verified commitment to `amount`, which payment proofs are bound to
+    pub amount_commitment: Option<[u8; 32]>,
+}
+
+/// Outcome of a single employee payout
+pub struct Payout {
+    /// Tokens sent to the employee, including any transfer fee
+    pub gross_amount: u64,
+
+    /// Transfer fee withheld by the mint and covered by the treasury
+    pub fee_withheld: u64,
+
+    /// Protocol fee routed to the fee vault
+    pub protocol_fee: u64,
+
+    /// Whether the payout settled a removed employee's final wages
+    pub final_payment: bool,
+
+    /// Total routed to the employee's withholding vaults
+    pub withheld: u64,
+
+    /// Advance repayment withheld from the payment and kept in the treasury
+    pub advance_repayment: u64,
+
+    /// Whether this payment cleared the employee's advance
+    pub advance_repaid: bool,
+}
+
+/// Outcome of moving tokens from the treasury to a payee
+pub struct Disbursement {
+    /// Tokens sent to the payee, including any transfer fee
+    pub gross_amount: u64,
+
+    /// Transfer fee withheld by the mint and covered by the treasury
+    pub fee_withheld: u64,
+
+    /// Protocol fee routed to the fee vault
+    pub protocol_fee: u64,
+}
+
+/// Sends `amount` (net of transfer fees) from the treasury to `destination`,
+/// charging the protocol fee and enforcing balance and spending cap.
+pub fn disburse<'info>(
+    accounts: &mut PayrollAccounts<'_, 'info>,
+    payee: Pubkey,
+    destination: AccountInfo<'info>,
+    amount: u64,
+    current_time: i64,
+) -> Result<Disbursement> {
+    // Gross up transfer-fee mints so the payee receives the full amount
+    let gross_amount = gross_up_for_fee(accounts.mint, amount)?;
+    let fee_withheld = gross_amount - amount;
+
+    // Protocol fee is charged to the company on top of the payment
+    let protocol_fee = accounts.protocol_config.fee_for(amount)?;
+    let total_outflow = gross_amount
+        .checked_add(protocol_fee)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    // Check if company treasury has sufficient balance
+    let balance_before = accounts.treasury.amount;
+    require!(
+        balance_before >= total_outflow,
+        GhostPayrollError::InsufficientCompanyBalance
+    );
+    let balance_after = balance_before - total_outflow;
+
+    // Enforce the rolling spending cap
+    accounts
+        .treasury_ledger
+        .record_outflow(total_outflow, current_time)?;
+
+    // Transfer tokens from treasury to payee
+    transfer_from_treasury(
+        accounts.token_program,
+        accounts.treasury,
+        accounts.mint,
+        destination,
+        accounts.company,
+        gross_amount,
+    )?;
+
+    // Route the protocol fee into the fee vault
+    if protocol_fee > 0 {
+        transfer_from_treasury(
+            accounts.token_program,
+            accounts.treasury,
+            accounts.mint,
+            accounts.fee_vault.to_account_info(),
+            accounts.company,
+            protocol_fee,
+        )?;
+
+        emit!(ProtocolFeeCollected {
+            company: accounts.company.key(),
+            employee: payee,
+            mint: accounts.mint.key(),
+            payment_amount: amount,
+            fee_amount: protocol_fee,
+            fee_bps: accounts.protocol_config.fee_bps,
+            timestamp: current_time,
+        });
+    }
+
+    // Keep the cached balance current for any later payout in the same instruction
+    accounts.treasury.reload()?;
+
+    // Warn when this payment leaves the treasury short of its committed runway
+    let treasury_ledger = &*accounts.treasury_ledger;
+    if treasury_ledger.crossed_low_runway(balance_before, balance_after) {
+        emit!(LowRunway {
+            company: accounts.company.key(),
+            mint: treasury_ledger.mint,
+            treasury_balance: balance_after,
+            period_obligation: treasury_ledger.period_obligation,
+            runway_periods: treasury_ledger.runway_periods(balance_after),
+            low_runway_threshold: treasury_ledger.low_runway_threshold,
+            timestamp: current_time,
+        });
+    }
+
+    Ok(Disbursement {
+        gross_amount,
+        fee_withheld,
+        protocol_fee,
+    })
+}
+
+/// Moves `amount` (net of transfer fees) from the treasury into a company
+/// withholding vault, enforcing the balance. Neither the protocol fee nor the
+/// spending cap applies since the tokens stay under company control; the cap
+/// is charged once they are remitted out of the vault.
+fn transfer_withholding<'info>(
+    accounts: &mut PayrollAccounts<'_, 'info>,
+    vault: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let gross_amount = gross_up_for_fee(accounts.mint, amount)?;
+    require!(
+        accounts.treasury.amount >= gross_amount,
+        GhostPayrollError::InsufficientCompanyBalance
+    );
+
+    transfer_from_treasury(
+        accounts.token_program,
+        accounts.treasury,
+        accounts.mint,
+        vault,
+        accounts.company,
+        gross_amount,
+    )?;
+
+    accounts.treasury.reload()?;
+    Ok(())
+}
+
+/// Pays `request.amount` to an employee out of the company treasury, applying
+/// fees, the spending cap and the employee's pay schedule, and updates all records.
+///
+/// Salary and final payments must equal `full_period_amount` prorated over
+/// the part of the oldest unpaid pay period the employee was employed, so a
+/// removed employee is paid one overdue period at a time; other categories
+/// are paid off-cycle. Withheld categories are split between the employee and
+/// `withholding_vaults` (one per withholding line, in order), and salary also
+/// repays any outstanding `advance` out of the net.
+pub fn pay_employee<'info>(
+    accounts: &mut PayrollAccounts<'_, 'info>,
+    employee: &mut Employee,
+    advance: Option<&mut Advance>,
+    employee_token_account: AccountInfo<'info>,
+    withholding_vaults: &[AccountInfo<'info>],
+    request: PaymentRequest,
+    current_time: i64,
+) -> Result<Payout> {
+    let PaymentRequest {
+        amount,
+        full_period_amount,
+        category,
+        amount_commitment,
+    } = request;
+    let off_cycle = category.is_off_cycle();
+
+    if !off_cycle {
+        check_on_cycle_payment(
+            accounts.company,
+            employee,
+            accounts.payroll_run.is_some(),
+            current_time,
+        )?;
+    }
+
+    // Joining or leaving mid-period earns only the worked share of the period
+    let proration = if off_cycle {
+        None
+    } else {
+        let basis = employee.proration_basis();
+        require!(
+            amount == basis.prorate(full_period_amount)?,
+            GhostPayrollError::InvalidProratedAmount
+        );
+        Some(basis)
+    };
+
+    // Validate amount; a partial period may prorate below the minimum, or
+    // final wages for a short stint could never be settled
+    require!(
+        amount >= MIN_SALARY_AMOUNT || proration.is_some_and(|basis| basis.is_partial()),
+        GhostPayrollError::InvalidSalaryAmount
+    );
+
+    // A payroll run pays each employee at most once
+    if let Some(payroll_run) = accounts.payroll_run.as_deref_mut() {
+        payroll_run.record_payment(employee.employee_index, amount)?;
+    }
+
+    // Split withholdings off the gross into their vaults
+    let mut withholdings = Vec::new();
+    let mut withheld = 0u64;
+    if category.is_withheld() && !employee.withholding_lines.is_empty() {
+        require!(
+            withholding_vaults.len() == employee.withholding_lines.len(),
+            GhostPayrollError::WithholdingVaultMismatch
+        );
+        for (line, vault) in employee
+            .withholding_lines
+            .iter_mut()
+            .zip(withholding_vaults)
+        {
+            require_keys_eq!(vault.key(), line.vault, GhostPayrollError::WithholdingVaultMismatch);
+
+            let line_amount = line.rate.amount_for(amount)?;
+            withheld = withheld
+                .checked_add(line_amount)
+                .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+            require!(withheld <= amount, GhostPayrollError::WithholdingExceedsPayment);
+
+            if line_amount > 0 {
+                transfer_withholding(accounts, vault.clone(), line_amount)?;
+                line.total_withheld = line
+                    .total_withheld
+                    .checked_add(line_amount)
+                    .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+            }
+            withholdings.push(WithholdingAmount {
+                name: line.name.clone(),
+                vault: line.vault,
+                amount: line_amount,
+            });
+        }
+    }
+
+    // Salary pays down an outstanding advance out of the net; the withheld
+    // part stays in the treasury
+    let mut advance_repayment = 0;
+    let mut advance_outstanding = 0;
+    let mut advance_principal = 0;
+    if employee.has_advance && category == PaymentCategory::Salary {
+        let advance = advance.ok_or(GhostPayrollError::AdvanceAccountRequired)?;
+        advance_repayment = advance.withhold_repayment(amount - withheld);
+        advance_outstanding = advance.outstanding;
+        advance_principal = advance.principal;
+    }
+    let advance_repaid = advance_repayment > 0 && advance_outstanding == 0;
+
+    let net_amount = amount - withheld - advance_repayment;
+    let disbursement = if net_amount > 0 {
+        disburse(
+            accounts,
+            employee.wallet,
+            employee_token_account,
+            net_amount,
+            current_time,
+        )?
+    } else {
+        Disbursement {
+            gross_amount: 0,
+            fee_withheld: 0,
+            protocol_fee: 0,
+        }
+    };
+
+    let company = &mut *accounts.company;
+
+    if !withholdings.is_empty() {
+        emit!(PaymentWithheld {
+            company: company.key(),
+            employee: employee.wallet,
+            category,
+            gross_amount: amount,
+            net_amount: amount - withheld,
+            withholdings,
+            timestamp: current_time,
+        });
+    }
+
+    if advance_repayment > 0 {
+        emit!(AdvanceRepaymentWithheld {
+            company: company.key(),
+            employee: employee.wallet,
+            repayment: advance_repayment,
+            outstanding: advance_outstanding,
+            timestamp: current_time,
+        });
+    }
+    if advance_repaid {
+        employee.has_advance = false;
+        emit!(AdvanceRepaid {
+            company: company.key(),
+            employee: employee.wallet,
+            principal: advance_principal,
+            timestamp: current_time,
+        });
+        msg!("Advance repaid");
+    }
+
+    // Update employee and company payment records
+    record_payment(company, employee, category, amount_commitment, current_time)?;
+    employee.record_category_payment(category, amount)?;
+
+    // Salary settles the period it covered and moves the schedule on
+    let mut final_payment = false;
+    if let Some(basis) = proration {
+        final_payment = settle_pay_period(company, employee, basis)?;
+        if basis.is_partial() {
+            emit!(PaymentProrated {
+                company: company.key(),
+                employee: employee.wallet,
+                amount,
+                full_period_amount,
+                period_start: basis.period_start,
+                period_end: basis.period_end,
+                worked_seconds: basis.worked_seconds,
+                final_payment,
+                timestamp: current_time,
+            });
+        }
+    }
+
+    // Off-cycle payments leave the regular schedule untouched
+    if off_cycle {
+        emit!(OffCyclePaymentProcessed {
+            company: company.key(),
+            employee: employee.wallet,
+            mint: employee.payout_mint,
+            category,
+            amount,
+            next_payment_due: employee.next_payment_due,
+            timestamp: current_time,
+        });
+    }
+
+    Ok(Payout {
+        gross_amount: disbursement.gross_amount,
+        fee_withheld: disbursement.fee_withheld,
+        protocol_fee: disbursement.protocol_fee,
+        final_payment,
+        withheld,
+        advance_repayment,
+        advance_repaid,
+    })
+}
+
+/// Fails unless an on-cycle payment may be made now: recorded in the open
+/// payroll run if there is one, to an employee not paid by stream, and due
+/// on their schedule unless it settles final wages
+pub fn check_on_cycle_payment(
+    company: &Company,
+    employee: &Employee,
+    in_payroll_run: bool,
+    current_time: i64,
+) -> Result<()> {
+    // While a run is open, on-cycle payments must be recorded in it so no
+    // employee can be paid twice for the period
+    require!(
+        !company.has_open_payroll_run || in_payroll_run,
+        GhostPayrollError::PayrollRunRequired
+    );
+
+    // A streamed employee already earns their salary by the second
+    require!(employee.stream_rate == 0, GhostPayrollError::EmployeeStreamed);
+
+    // Regular payments follow the employee's schedule; final wages are owed immediately
+    if employee.is_active {
+        require!(
+            employee.is_payment_due(current_time),
+            GhostPayrollError::PaymentNotDue
+        );
+    }
+    Ok(())
+}
+
+/// Closes out the pay period an on-cycle payment covered and moves the
+/// employee's schedule on. Returns whether the payment settled a removed
+/// employee's final wages, which leaves the schedule where it is.
+pub fn settle_pay_period(
+    company: &mut Company,
+    employee: &mut Employee,
+    basis: ProrationBasis,
+) -> Result<bool> {
+    // A removed employee is paid each overdue period in turn; only the salary
+    // for the period they left in settles their final wages
+    let final_payment = !employee.is_active && employee.termination_date <= basis.period_end;
+    if final_payment {
+        employee.final_payment_pending = false;
+        company.pending_final_payments = company
+            .pending_final_payments
+            .checked_sub(1)
+            .ok_or(GhostPayrollError::ArithmeticUnderflow)?;
+        msg!("Final payment settled");
+    }
+
+    // Record how the payment was prorated before the schedule moves on
+    employee.last_proration = basis;
+    employee.paid_through = basis.period_end;
+    if !final_payment {
+        employee.advance_payment_schedule();
+    }
+    Ok(final_payment)
+}
+
+/// Updates the payment counters every payout shares, plaintext or
+/// confidential: the employee's payment number, last payment (with its
+/// commitment and category) and off-cycle count, and the company's payment total and payroll schedule
+pub fn record_payment(
+    company: &mut Company,
+    employee: &mut Employee,
+    category: PaymentCategory,
+    amount_commitment: Option<[u8; 32]>,
+    current_time: i64,
+) -> Result<()> {
+    employee.last_payment_date = current_time;
+    employee.total_payments_received = employee
+        .total_payments_received
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    employee.last_payment_commitment = amount_commitment.unwrap_or_default();
+    employee.last_payment_category = category;
+    if category.is_off_cycle() {
+        employee.off_cycle_payment_count = employee
+            .off_cycle_payment_count
+            .checked_add(1)
+            .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    }
+
+    company.last_payment_timestamp = current_time;
+    company.advance_payroll_schedule(current_time);
+    company.total_payments_made = company
+        .total_payments_made
+        .checked_add(1)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+    Ok(())
+}
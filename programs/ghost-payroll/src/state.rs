@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::calendar::*;
+use crate::commitment::*;
+use crate::constants::*;
+use crate::errors::*;
+
 // ==================== COMPANY ACCOUNT ====================
 #[account]
 #[derive(Default)]
@@ -13,15 +18,34 @@ pub struct Company {
     /// Number of active employees
     pub employee_count: u16,
 
-    /// Total monthly budget (stored encrypted off-chain, this is a commitment)
+    /// Pedersen commitment to the total payroll budget per pay period
     pub budget_commitment: [u8; 32],
 
-    /// Token mint for payments (e.g., USDC)
+    /// Default token mint for payments (e.g., USDC), whose treasury is created at initialization
     pub payment_token: Pubkey,
 
+    /// Number of mints the company holds a treasury for
+    pub treasury_count: u8,
+
+    /// Number of removed employees whose final wages are still unpaid
+    pub pending_final_payments: u16,
+
+    /// Number of removed employees with streamed wages not yet withdrawn
+    pub pending_stream_withdrawals: u16,
+
+    /// Number of open withholding vaults
+    pub withholding_vault_count: u16,
+
+    /// Number of queued treasury withdrawals and remittances not yet executed or cancelled
+    pub pending_withdrawals: u16,
+
     /// How often employees get paid
     pub payment_frequency: PaymentFrequency,
 
+    /// Unix timestamp the pay calendar is anchored to (first payday, and the
+    /// day-of-month and time of day for monthly and quarterly schedules)
+    pub pay_anchor: i64,
+
     /// Unix timestamp of last payroll run
     pub last_payment_timestamp: i64,
 
@@ -31,6 +55,37 @@ pub struct Company {
     /// Total payments made (count)
     pub total_payments_made: u64,
 
+    /// Seconds a queued treasury withdrawal must wait before execution
+    pub withdrawal_delay: i64,
+
+    /// Number of treasury withdrawals queued (used as the next withdrawal ID)
+    pub withdrawal_count: u64,
+
+    /// Whether the company's authority has halted all company operations
+    pub paused: bool,
+
+    /// Reason code supplied when the company was paused
+    pub pause_reason: u16,
+
+    /// Unix timestamp when the company was paused
+    pub paused_at: i64,
+
+    /// Index assigned to the next employee added (never reused)
+    pub next_employee_index: u16,
+
+    /// Number of payroll runs opened (used as the next run's period number)
+    pub payroll_run_count: u64,
+
+    /// Whether a payroll run is currently open
+    pub has_open_payroll_run: bool,
+
+    /// Homomorphic sum of active employees' salary commitments
+    pub salary_commitment_total: [u8; 32],
+
+    /// Unix timestamp the budget was last proven to cover the salary total
+    /// (0 once either commitment changes)
+    pub budget_verified_at: i64,
+
     /// Whether company is active
     pub is_active: bool,
 
@@ -45,15 +100,55 @@ impl Company {
         2 +   // employee_count
         32 +  // budget_commitment
         32 +  // payment_token
+        1 +   // treasury_count
+        2 +   // pending_final_payments
+        2 +   // pending_stream_withdrawals
+        2 +   // withholding_vault_count
+        2 +   // pending_withdrawals
         1 +   // payment_frequency
+        8 +   // pay_anchor
         8 +   // last_payment_timestamp
         8 +   // next_payment_due
         8 +   // total_payments_made
+        8 +   // withdrawal_delay
+        8 +   // withdrawal_count
+        1 +   // paused
+        2 +   // pause_reason
+        8 +   // paused_at
+        2 +   // next_employee_index
+        8 +   // payroll_run_count
+        1 +   // has_open_payroll_run
+        32 +  // salary_commitment_total
+        8 +   // budget_verified_at
         1 +   // is_active
         1 +   // bump
         64;   // padding
 
     pub const MAX_NAME_LENGTH: usize = 50;
+
+    /// Adds an active employee's salary commitment to the company total
+    pub fn add_salary_commitment(&mut self, commitment: &[u8; 32]) -> Result<()> {
+        self.salary_commitment_total = add_commitments(&self.salary_commitment_total, commitment)?;
+        self.budget_verified_at = 0;
+        Ok(())
+    }
+
+    /// Removes a salary commitment previously added to the company total
+    pub fn remove_salary_commitment(&mut self, commitment: &[u8; 32]) -> Result<()> {
+        self.salary_commitment_total =
+            subtract_commitments(&self.salary_commitment_total, commitment)?;
+        self.budget_verified_at = 0;
+        Ok(())
+    }
+
+    /// Moves the company payroll date past `now` on the company's own schedule
+    pub fn advance_payroll_schedule(&mut self, now: i64) {
+        if now >= self.next_payment_due {
+            self.next_payment_due = self
+                .payment_frequency
+                .next_payment_after(self.pay_anchor, now);
+        }
+    }
 }
 
 // ==================== EMPLOYEE ACCOUNT ====================
@@ -66,16 +161,25 @@ pub struct Employee {
     /// Associated company
     pub company: Pubkey,
 
+    /// Position of the employee in payroll run bitmaps
+    pub employee_index: u16,
+
     /// Encrypted salary amount (encrypted via client-side encryption)
     /// Only company authority and employee can decrypt
     pub encrypted_salary: Vec<u8>,
 
-    /// Salary commitment hash for verification
+    /// Commitment to the full per-period salary; on-cycle payments must open it
     pub salary_commitment: [u8; 32],
 
+    /// Token mint the employee is paid in
+    pub payout_mint: Pubkey,
+
     /// Payment frequency for this employee
     pub payment_frequency: PaymentFrequency,
 
+    /// Pay calendar anchor, copied from the company at hire
+    pub pay_anchor: i64,
+
     /// Unix timestamp when employee joined
     pub join_date: i64,
 
@@ -85,9 +189,69 @@ pub struct Employee {
     /// Total number of payments received
     pub total_payments_received: u64,
 
+    /// Commitment to the amount of the last payment (zero if it was made without one)
+    pub last_payment_commitment: [u8; 32],
+
+    /// What the last payment was for
+    pub last_payment_category: PaymentCategory,
+
+    /// Unix timestamp when the employee's next regular payment is due
+    pub next_payment_due: i64,
+
+    /// Unix timestamp up to which regular wages have been paid
+    pub paid_through: i64,
+
+    /// Number of payments made outside the regular schedule
+    pub off_cycle_payment_count: u32,
+
+    /// Number of confidential payments, whose amounts stay out of the per-category totals
+    pub confidential_payment_count: u32,
+
+    /// Number of payments received in each `PaymentCategory`
+    pub payments_by_category: [u32; 5],
+
+    /// Total amount received in each `PaymentCategory`
+    pub amount_by_category: [u64; 5],
+
+    /// Proration basis of the last regular or final payment
+    pub last_proration: ProrationBasis,
+
+    /// Whether an `Advance` is outstanding and must be repaid from salary
+    pub has_advance: bool,
+
+    /// Withholdings split off each salary, bonus and commission payment
+    pub withholding_lines: Vec<WithholdingLine>,
+
     /// Whether employee is active
     pub is_active: bool,
 
+    /// Unix timestamp when the employee was removed (0 while active)
+    pub termination_date: i64,
+
+    /// Whether final wages are still owed after removal
+    pub final_payment_pending: bool,
+
+    /// Total tokens returned to the treasury through payment corrections
+    pub total_clawed_back: u64,
+
+    /// Number of completed payment corrections
+    pub correction_count: u32,
+
+    /// Streaming rate in tokens per second (0 when not streaming)
+    pub stream_rate: u64,
+
+    /// Whether accrual is paused
+    pub stream_paused: bool,
+
+    /// Unix timestamp up to which streamed wages have been accrued
+    pub stream_settled_at: i64,
+
+    /// Wages accrued up to `stream_settled_at` and not yet withdrawn
+    pub stream_accrued: u64,
+
+    /// Total streamed wages withdrawn by the employee
+    pub total_streamed: u64,
+
     /// Bump seed for PDA
     pub bump: u8,
 }
@@ -96,17 +260,105 @@ impl Employee {
     pub const LEN: usize = 8 + // discriminator
         32 +  // wallet
         32 +  // company
+        2 +   // employee_index
         (4 + 256) + // encrypted_salary (Vec with max 256 bytes)
         32 +  // salary_commitment
+        32 +  // payout_mint
         1 +   // payment_frequency
+        8 +   // pay_anchor
         8 +   // join_date
         8 +   // last_payment_date
         8 +   // total_payments_received
+        32 +  // last_payment_commitment
+        1 +   // last_payment_category
+        8 +   // next_payment_due
+        8 +   // paid_through
+        4 +   // off_cycle_payment_count
+        4 +   // confidential_payment_count
+        (4 * PaymentCategory::COUNT) + // payments_by_category
+        (8 * PaymentCategory::COUNT) + // amount_by_category
+        ProrationBasis::LEN + // last_proration
+        1 +   // has_advance
+        (4 + MAX_WITHHOLDING_LINES * WithholdingLine::LEN) + // withholding_lines
         1 +   // is_active
+        8 +   // termination_date
+        1 +   // final_payment_pending
+        8 +   // total_clawed_back
+        4 +   // correction_count
+        8 +   // stream_rate
+        1 +   // stream_paused
+        8 +   // stream_settled_at
+        8 +   // stream_accrued
+        8 +   // total_streamed
         1 +   // bump
         32;   // padding
 
     pub const MAX_ENCRYPTED_SALARY_SIZE: usize = 256;
+
+    /// Whether the employee's next regular payment is due at `now`
+    pub fn is_payment_due(&self, now: i64) -> bool {
+        now >= self.next_payment_due
+    }
+
+    /// Adds a payment to the employee's per-category totals
+    pub fn record_category_payment(&mut self, category: PaymentCategory, amount: u64) -> Result<()> {
+        let index = category.index();
+        self.payments_by_category[index] = self.payments_by_category[index]
+            .checked_add(1)
+            .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+        self.amount_by_category[index] = self.amount_by_category[index]
+            .checked_add(amount)
+            .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Pay period ending at the employee's next payday, and how much of it
+    /// falls between the end of the last paid period (or the join date) and
+    /// the termination date (if any)
+    pub fn proration_basis(&self) -> ProrationBasis {
+        let period_end = self.next_payment_due;
+        let period_start = self
+            .payment_frequency
+            .previous_payment_before(self.pay_anchor, period_end);
+        let worked_from = self.join_date.max(self.paid_through).max(period_start);
+        let worked_until = if self.termination_date > 0 {
+            self.termination_date.min(period_end)
+        } else {
+            period_end
+        };
+        ProrationBasis {
+            period_start,
+            period_end,
+            worked_seconds: (worked_until - worked_from).max(0),
+        }
+    }
+
+    /// Advances the employee's schedule by one pay period
+    pub fn advance_payment_schedule(&mut self) {
+        self.next_payment_due = self
+            .payment_frequency
+            .next_payment_after(self.pay_anchor, self.next_payment_due);
+    }
+
+    /// Streamed wages available for withdrawal at `now`
+    pub fn accrued_stream(&self, now: i64) -> Result<u64> {
+        if self.stream_paused || self.stream_rate == 0 || now <= self.stream_settled_at {
+            return Ok(self.stream_accrued);
+        }
+        let elapsed = (now - self.stream_settled_at) as u64;
+        self.stream_rate
+            .checked_mul(elapsed)
+            .and_then(|earned| earned.checked_add(self.stream_accrued))
+            .ok_or(GhostPayrollError::ArithmeticOverflow.into())
+    }
+
+    /// Folds wages streamed up to `now` into `stream_accrued`; call before
+    /// changing the rate, pausing, resuming or terminating the stream
+    pub fn settle_stream(&mut self, now: i64) -> Result<()> {
+        self.stream_accrued = self.accrued_stream(now)?;
+        self.stream_settled_at = now;
+        Ok(())
+    }
 }
 
 // ==================== PAYMENT PROOF ACCOUNT ====================
@@ -128,7 +380,8 @@ pub struct PaymentProof {
     /// Commitment hash of payment amount (for verification without revealing)
     pub amount_commitment: [u8; 32],
 
-    /// ZK proof of payment (proves payment was made correctly)
+    /// Groth16 proof that the committed amount was paid to the employee,
+    /// verified against the company's registered key
     pub zk_proof: Vec<u8>,
 
     /// ShadowWire transaction signature
@@ -137,6 +390,12 @@ pub struct PaymentProof {
     /// Payment status
     pub status: PaymentStatus,
 
+    /// What the payment was for
+    pub category: PaymentCategory,
+
+    /// Tokens returned to the treasury by a payment correction (0 if none)
+    pub clawed_back_amount: u64,
+
     /// Bump seed for PDA
     pub bump: u8,
 }
@@ -151,6 +410,8 @@ impl PaymentProof {
         (4 + 512) + // zk_proof (Vec with max 512 bytes)
         (4 + 88) + // shadowwire_tx_signature (String with 88 chars)
         1 +   // status
+        1 +   // category
+        8 +   // clawed_back_amount
         1 +   // bump
         32;   // padding
 
@@ -158,26 +419,654 @@ impl PaymentProof {
     pub const MAX_SIGNATURE_LENGTH: usize = 88;
 }
 
+// ==================== TREASURY LEDGER ACCOUNT ====================
+#[account]
+#[derive(Default)]
+pub struct TreasuryLedger {
+    /// Company that owns the treasury
+    pub company: Pubkey,
+
+    /// Token mint held by the treasury
+    pub mint: Pubkey,
+
+    /// Treasury token account for this mint
+    pub treasury: Pubkey,
+
+    /// Cumulative tokens credited to the treasury via fund_treasury (net of transfer fees)
+    pub total_deposited: u64,
+
+    /// Number of treasury deposits made
+    pub deposit_count: u64,
+
+    /// Unix timestamp of last treasury deposit
+    pub last_deposit_timestamp: i64,
+
+    /// Tokens committed to payroll per company pay period (declared as an
+    /// aggregate so individual salaries are not revealed)
+    pub period_obligation: u64,
+
+    /// Runway (in pay periods) below which a low-runway event is emitted
+    pub low_runway_threshold: u16,
+
+    /// Maximum tokens that may leave the treasury per rolling window (0 = unlimited).
+    /// Kept per mint because token units of different mints are not comparable.
+    pub spending_cap: u64,
+
+    /// Raised cap waiting out the timelock (only meaningful when pending_cap_effective_at != 0)
+    pub pending_spending_cap: u64,
+
+    /// Unix timestamp when the pending cap takes effect (0 = none pending)
+    pub pending_cap_effective_at: i64,
+
+    /// Day index (unix days) of the most recent outflow bucket
+    pub outflow_day: i64,
+
+    /// Outflow per day over the rolling window, indexed by day modulo the window
+    pub outflow_buckets: [u64; SPENDING_CAP_WINDOW_DAYS],
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl TreasuryLedger {
+    pub const LEN: usize = 8 + // discriminator
+        32 +  // company
+        32 +  // mint
+        32 +  // treasury
+        8 +   // total_deposited
+        8 +   // deposit_count
+        8 +   // last_deposit_timestamp
+        8 +   // period_obligation
+        2 +   // low_runway_threshold
+        8 +   // spending_cap
+        8 +   // pending_spending_cap
+        8 +   // pending_cap_effective_at
+        8 +   // outflow_day
+        (8 * SPENDING_CAP_WINDOW_DAYS) + // outflow_buckets
+        1 +   // bump
+        64;   // padding
+
+    /// Returns how many full pay periods `balance` covers at the committed obligation
+    pub fn runway_periods(&self, balance: u64) -> u64 {
+        if self.period_obligation == 0 {
+            return u64::MAX;
+        }
+        balance / self.period_obligation
+    }
+
+    /// Returns true if an outflow taking the treasury from `balance_before`
+    /// to `balance_after` pushes the runway below the low-runway threshold
+    pub fn crossed_low_runway(&self, balance_before: u64, balance_after: u64) -> bool {
+        let threshold = u64::from(self.low_runway_threshold);
+        self.runway_periods(balance_before) >= threshold
+            && self.runway_periods(balance_after) < threshold
+    }
+
+    /// Whether outflows are capped now or will be once a pending cap takes effect
+    pub fn has_spending_cap(&self) -> bool {
+        self.spending_cap != 0 || self.pending_cap_effective_at != 0
+    }
+
+    /// Promotes a pending cap increase once its timelock has elapsed
+    pub fn apply_pending_spending_cap(&mut self, now: i64) {
+        if self.pending_cap_effective_at != 0 && now >= self.pending_cap_effective_at {
+            self.spending_cap = self.pending_spending_cap;
+            self.pending_spending_cap = 0;
+            self.pending_cap_effective_at = 0;
+        }
+    }
+
+    /// Returns the tokens that left the treasury during the rolling window ending on `day`
+    pub fn rolling_outflow(&self, day: i64) -> u64 {
+        let window = SPENDING_CAP_WINDOW_DAYS as i64;
+        (0..window)
+            .map(|offset| self.outflow_day - offset)
+            .filter(|bucket_day| day - bucket_day < window)
+            .map(|bucket_day| self.outflow_buckets[bucket_day.rem_euclid(window) as usize])
+            .sum()
+    }
+
+    /// Checks `amount` against the spending cap and records it as an outflow
+    pub fn record_outflow(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.apply_pending_spending_cap(now);
+
+        let window = SPENDING_CAP_WINDOW_DAYS as i64;
+        let day = now.div_euclid(SECONDS_PER_DAY);
+
+        if self.spending_cap != 0 {
+            let spent = self.rolling_outflow(day);
+            let total = spent
+                .checked_add(amount)
+                .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+            require!(
+                total <= self.spending_cap,
+                GhostPayrollError::SpendingCapExceeded
+            );
+        }
+
+        // Clear buckets for the days that passed since the last outflow
+        if day - self.outflow_day >= window {
+            self.outflow_buckets = [0; SPENDING_CAP_WINDOW_DAYS];
+        } else {
+            for stale_day in (self.outflow_day + 1)..=day {
+                self.outflow_buckets[stale_day.rem_euclid(window) as usize] = 0;
+            }
+        }
+        self.outflow_day = self.outflow_day.max(day);
+
+        let bucket = &mut self.outflow_buckets[day.rem_euclid(window) as usize];
+        *bucket = bucket
+            .checked_add(amount)
+            .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+}
+
+// ==================== TREASURY WITHDRAWAL ACCOUNT ====================
+#[account]
+#[derive(Default)]
+pub struct TreasuryWithdrawal {
+    /// Company the withdrawal is drawn from
+    pub company: Pubkey,
+
+    /// Sequential withdrawal ID within the company
+    pub withdrawal_id: u64,
+
+    /// Token mint of the treasury being withdrawn from
+    pub mint: Pubkey,
+
+    /// Token account drawn from: the treasury, or a withholding vault for remittances
+    pub source: Pubkey,
+
+    /// Amount of tokens to withdraw
+    pub amount: u64,
+
+    /// Token account that receives the withdrawn funds
+    pub destination: Pubkey,
+
+    /// Unix timestamp when the withdrawal was queued
+    pub requested_at: i64,
+
+    /// Unix timestamp after which the withdrawal can be executed
+    pub executable_at: i64,
+
+    /// Withdrawal status
+    pub status: WithdrawalStatus,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl TreasuryWithdrawal {
+    pub const LEN: usize = 8 + // discriminator
+        32 +  // company
+        8 +   // withdrawal_id
+        32 +  // mint
+        32 +  // source
+        8 +   // amount
+        32 +  // destination
+        8 +   // requested_at
+        8 +   // executable_at
+        1 +   // status
+        1 +   // bump
+        32;   // padding
+}
+
+// ==================== PAYMENT CORRECTION ACCOUNT ====================
+#[account]
+#[derive(Default)]
+pub struct PaymentCorrection {
+    /// Company that made the original payment
+    pub company: Pubkey,
+
+    /// Employee wallet that received the original payment
+    pub employee: Pubkey,
+
+    /// Payment proof being corrected
+    pub payment_proof: Pubkey,
+
+    /// ID of the payment being corrected
+    pub payment_id: u64,
+
+    /// Token mint the return is made in
+    pub mint: Pubkey,
+
+    /// Tokens the employee is asked to return to the treasury
+    pub amount: u64,
+
+    /// Unix timestamp when the correction was opened
+    pub opened_at: i64,
+
+    /// Unix timestamp when the employee returned the tokens (0 until then)
+    pub completed_at: i64,
+
+    /// Correction status
+    pub status: CorrectionStatus,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PaymentCorrection {
+    pub const LEN: usize = 8 + // discriminator
+        32 +  // company
+        32 +  // employee
+        32 +  // payment_proof
+        8 +   // payment_id
+        32 +  // mint
+        8 +   // amount
+        8 +   // opened_at
+        8 +   // completed_at
+        1 +   // status
+        1 +   // bump
+        32;   // padding
+}
+
+// ==================== PAYROLL RUN ACCOUNT ====================
+#[account]
+pub struct PayrollRun {
+    /// Company the run belongs to
+    pub company: Pubkey,
+
+    /// Sequential period number of the run
+    pub period: u64,
+
+    /// Run status
+    pub status: PayrollRunStatus,
+
+    /// Unix timestamp when the run was opened
+    pub opened_at: i64,
+
+    /// Unix timestamp when the run was finalized (0 while open)
+    pub finalized_at: i64,
+
+    /// Number of times the run was resumed after an interruption
+    pub resume_count: u16,
+
+    /// Number of employees paid in the run
+    pub payments_made: u16,
+
+    /// Total tokens paid to employees in the run
+    pub total_paid: u64,
+
+    /// One bit per employee index, set once that employee is paid
+    pub paid_bitmap: [u8; PAYROLL_RUN_BITMAP_BYTES],
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PayrollRun {
+    pub const LEN: usize = 8 + // discriminator
+        32 +  // company
+        8 +   // period
+        1 +   // status
+        8 +   // opened_at
+        8 +   // finalized_at
+        2 +   // resume_count
+        2 +   // payments_made
+        8 +   // total_paid
+        PAYROLL_RUN_BITMAP_BYTES + // paid_bitmap
+        1 +   // bump
+        32;   // padding
+
+    /// Whether the employee at `index` has been paid in this run
+    pub fn is_paid(&self, index: u16) -> bool {
+        let index = index as usize;
+        self.paid_bitmap[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Records a payment of `amount` to the employee at `index`, failing if
+    /// they were already paid in this run
+    pub fn record_payment(&mut self, index: u16, amount: u64) -> Result<()> {
+        require!(
+            (index as usize) < PAYROLL_RUN_BITMAP_BYTES * 8,
+            GhostPayrollError::EmployeeNotFound
+        );
+        require!(!self.is_paid(index), GhostPayrollError::PaymentAlreadyProcessed);
+
+        let index = index as usize;
+        self.paid_bitmap[index / 8] |= 1 << (index % 8);
+        self.payments_made = self
+            .payments_made
+            .checked_add(1)
+            .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+        self.total_paid = self
+            .total_paid
+            .checked_add(amount)
+            .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+// ==================== PAYROLL PLAN ACCOUNT ====================
+#[account]
+#[derive(Default)]
+pub struct PayrollPlan {
+    /// Company that approved the plan
+    pub company: Pubkey,
+
+    /// Employee wallet the plan pays
+    pub employee: Pubkey,
+
+    /// Token mint the plan pays in
+    pub mint: Pubkey,
+
+    /// Amount paid each period
+    pub amount: u64,
+
+    /// Commitment hash of the per-period amount
+    pub amount_commitment: [u8; 32],
+
+    /// Employee salary commitment the plan was approved against
+    pub salary_commitment: [u8; 32],
+
+    /// Number of periods the authority pre-approved
+    pub periods_approved: u16,
+
+    /// Number of approved periods already paid
+    pub periods_paid: u16,
+
+    /// Tip paid from the treasury to the keeper for each crank
+    pub keeper_tip: u64,
+
+    /// Unix timestamp when the plan was approved
+    pub approved_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PayrollPlan {
+    pub const LEN: usize = 8 + // discriminator
+        32 +  // company
+        32 +  // employee
+        32 +  // mint
+        8 +   // amount
+        32 +  // amount_commitment
+        32 +  // salary_commitment
+        2 +   // periods_approved
+        2 +   // periods_paid
+        8 +   // keeper_tip
+        8 +   // approved_at
+        1 +   // bump
+        32;   // padding
+}
+
+// ==================== ADVANCE ACCOUNT ====================
+#[account]
+#[derive(Default)]
+pub struct Advance {
+    /// Company that issued the advance
+    pub company: Pubkey,
+
+    /// Employee wallet the advance was paid to
+    pub employee: Pubkey,
+
+    /// Token mint the advance was paid in
+    pub mint: Pubkey,
+
+    /// Amount advanced
+    pub principal: u64,
+
+    /// Amount still to be repaid
+    pub outstanding: u64,
+
+    /// Amount withheld from each salary payment until repaid
+    pub repayment_per_period: u64,
+
+    /// Unix timestamp when the advance was issued
+    pub issued_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Advance {
+    pub const LEN: usize = 8 + // discriminator
+        32 +  // company
+        32 +  // employee
+        32 +  // mint
+        8 +   // principal
+        8 +   // outstanding
+        8 +   // repayment_per_period
+        8 +   // issued_at
+        1 +   // bump
+        32;   // padding
+
+    /// Withholds this period's repayment (capped at the outstanding balance
+    /// and the payment itself) and returns the amount withheld
+    pub fn withhold_repayment(&mut self, payment_amount: u64) -> u64 {
+        let repayment = self
+            .repayment_per_period
+            .min(self.outstanding)
+            .min(payment_amount);
+        self.outstanding -= repayment;
+        repayment
+    }
+}
+
+// ==================== VERIFYING KEY ACCOUNT ====================
+/// Groth16 verifying key for a company's payment circuit (encoded as in `groth16`)
+#[account]
+pub struct PaymentVerifyingKey {
+    /// Company that registered the key
+    pub company: Pubkey,
+
+    /// alpha in G1
+    pub alpha_g1: [u8; 64],
+
+    /// beta in G2
+    pub beta_g2: [u8; 128],
+
+    /// gamma in G2
+    pub gamma_g2: [u8; 128],
+
+    /// delta in G2
+    pub delta_g2: [u8; 128],
+
+    /// Input commitments in G1, one per public input plus the constant term
+    pub ic: [[u8; 64]; PAYMENT_PROOF_PUBLIC_INPUTS + 1],
+
+    /// Unix timestamp when the key was registered
+    pub registered_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PaymentVerifyingKey {
+    pub const LEN: usize = 8 + // discriminator
+        32 +  // company
+        64 +  // alpha_g1
+        128 + // beta_g2
+        128 + // gamma_g2
+        128 + // delta_g2
+        (64 * (PAYMENT_PROOF_PUBLIC_INPUTS + 1)) + // ic
+        8 +   // registered_at
+        1 +   // bump
+        32;   // padding
+}
+
+// ==================== PROTOCOL CONFIG ACCOUNT ====================
+#[account]
+#[derive(Default)]
+pub struct ProtocolConfig {
+    /// Admin allowed to change the fee and sweep collected fees
+    pub admin: Pubkey,
+
+    /// Fee charged on each payment, in basis points of the payment amount
+    pub fee_bps: u16,
+
+    /// Key allowed to pause and unpause the whole program
+    pub guardian: Pubkey,
+
+    /// Whether the guardian has halted all state-changing instructions
+    pub paused: bool,
+
+    /// Reason code supplied when the program was paused
+    pub pause_reason: u16,
+
+    /// Unix timestamp when the program was paused
+    pub paused_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 +  // admin
+        2 +   // fee_bps
+        32 +  // guardian
+        1 +   // paused
+        2 +   // pause_reason
+        8 +   // paused_at
+        1 +   // bump
+        64;   // padding
+
+    /// Returns the protocol fee owed on a payment of `amount`
+    pub fn fee_for(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(self.fee_bps as u128)
+            .ok_or(GhostPayrollError::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as u128;
+        u64::try_from(fee).map_err(|_| GhostPayrollError::ArithmeticOverflow.into())
+    }
+}
+
 // ==================== ENUMS ====================
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
 pub enum PaymentFrequency {
     #[default]
     Weekly,
     Biweekly,
+    /// Every month on the anchor's day-of-month, clamped for short months
     Monthly,
+    /// On the 15th and the last day of every month
+    SemiMonthly,
+    /// Every three months on the anchor's day-of-month, clamped for short months
+    Quarterly,
 }
 
 impl PaymentFrequency {
-    /// Returns seconds until next payment
-    pub fn seconds_to_next_payment(&self) -> i64 {
+    /// Returns the first payday strictly after `after` for a schedule anchored at `anchor`
+    pub fn next_payment_after(&self, anchor: i64, after: i64) -> i64 {
+        match self {
+            PaymentFrequency::Weekly => next_fixed_interval(anchor, SECONDS_PER_WEEK, after),
+            PaymentFrequency::Biweekly => next_fixed_interval(anchor, SECONDS_PER_BIWEEK, after),
+            PaymentFrequency::Monthly => next_monthly(anchor, 1, after),
+            PaymentFrequency::SemiMonthly => next_semi_monthly(anchor, after),
+            PaymentFrequency::Quarterly => next_monthly(anchor, 3, after),
+        }
+    }
+
+    /// Returns the last payday strictly before `before` for a schedule anchored at `anchor`
+    pub fn previous_payment_before(&self, anchor: i64, before: i64) -> i64 {
         match self {
-            PaymentFrequency::Weekly => 7 * 24 * 60 * 60,
-            PaymentFrequency::Biweekly => 14 * 24 * 60 * 60,
-            PaymentFrequency::Monthly => 30 * 24 * 60 * 60,
+            PaymentFrequency::Weekly => prev_fixed_interval(anchor, SECONDS_PER_WEEK, before),
+            PaymentFrequency::Biweekly => prev_fixed_interval(anchor, SECONDS_PER_BIWEEK, before),
+            PaymentFrequency::Monthly => prev_monthly(anchor, 1, before),
+            PaymentFrequency::SemiMonthly => prev_semi_monthly(anchor, before),
+            PaymentFrequency::Quarterly => prev_monthly(anchor, 3, before),
         }
     }
 }
 
+/// How a payment was prorated: the pay period and the part of it worked
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct ProrationBasis {
+    /// Start of the pay period (previous payday)
+    pub period_start: i64,
+
+    /// End of the pay period (payday)
+    pub period_end: i64,
+
+    /// Seconds of the period the employee was employed
+    pub worked_seconds: i64,
+}
+
+impl ProrationBasis {
+    pub const LEN: usize = 8 + 8 + 8;
+
+    /// Whether the employee was employed for only part of the period
+    pub fn is_partial(&self) -> bool {
+        self.worked_seconds < self.period_end - self.period_start
+    }
+
+    /// Share of `full_period_amount` earned over the worked part of the period
+    pub fn prorate(&self, full_period_amount: u64) -> Result<u64> {
+        let period_seconds = self.period_end - self.period_start;
+        if !self.is_partial() || period_seconds <= 0 {
+            return Ok(full_period_amount);
+        }
+        let amount = (full_period_amount as u128)
+            .checked_mul(self.worked_seconds.max(0) as u128)
+            .ok_or(GhostPayrollError::ArithmeticOverflow)?
+            / period_seconds as u128;
+        u64::try_from(amount).map_err(|_| GhostPayrollError::ArithmeticOverflow.into())
+    }
+}
+
+/// A share of an employee's gross pay routed to a company withholding vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct WithholdingLine {
+    /// Vault name (e.g. "federal_tax"), part of the vault's PDA seeds
+    pub name: String,
+
+    /// Withholding vault token account
+    pub vault: Pubkey,
+
+    /// How much of each payment is withheld
+    pub rate: WithholdingRate,
+
+    /// Total withheld under this line, awaiting remittance
+    pub total_withheld: u64,
+}
+
+impl WithholdingLine {
+    pub const LEN: usize = (4 + MAX_WITHHOLDING_NAME_LENGTH) + // name
+        32 +  // vault
+        WithholdingRate::LEN + // rate
+        8;    // total_withheld
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WithholdingRate {
+    /// Basis points of the gross payment
+    BasisPoints(u16),
+
+    /// Fixed amount per payment
+    Fixed(u64),
+}
+
+impl WithholdingRate {
+    pub const LEN: usize = 1 + 8;
+
+    /// Amount withheld from a payment of `gross_amount`
+    pub fn amount_for(&self, gross_amount: u64) -> Result<u64> {
+        match *self {
+            WithholdingRate::BasisPoints(bps) => {
+                let amount = (gross_amount as u128)
+                    .checked_mul(bps as u128)
+                    .ok_or(GhostPayrollError::ArithmeticOverflow)?
+                    / BPS_DENOMINATOR as u128;
+                Ok(amount as u64)
+            }
+            WithholdingRate::Fixed(amount) => Ok(amount),
+        }
+    }
+}
+
+/// One line of a payment's withholding breakdown
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct WithholdingAmount {
+    pub name: String,
+    pub vault: Pubkey,
+    pub amount: u64,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
 pub enum PaymentStatus {
     #[default]
@@ -185,4 +1074,60 @@ pub enum PaymentStatus {
     Processing,
     Completed,
     Failed,
+    Corrected,
+}
+
+/// What a payment was for; every category but `Salary` is paid off-cycle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum PaymentCategory {
+    #[default]
+    Salary,
+    Bonus,
+    Commission,
+    Reimbursement,
+    Adjustment,
+}
+
+impl PaymentCategory {
+    pub const COUNT: usize = 5;
+
+    /// Whether payments in this category bypass the regular pay schedule
+    pub fn is_off_cycle(&self) -> bool {
+        *self != PaymentCategory::Salary
+    }
+
+    /// Whether payments in this category are subject to withholding
+    pub fn is_withheld(&self) -> bool {
+        matches!(
+            self,
+            PaymentCategory::Salary | PaymentCategory::Bonus | PaymentCategory::Commission
+        )
+    }
+
+    /// Slot in the employee's per-category counters
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum WithdrawalStatus {
+    #[default]
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum CorrectionStatus {
+    #[default]
+    Pending,
+    Completed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum PayrollRunStatus {
+    #[default]
+    Open,
+    Finalized,
 }
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_interface::{
+    self, get_mint_extension_data, harvest_withheld_tokens_to_mint, HarvestWithheldTokensToMint,
+    Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+/// Returns the transfer fee the mint withholds when `amount` is sent.
+/// Mints without the Token-2022 transfer fee extension charge nothing.
+pub fn transfer_fee(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let Ok(fee_config) = get_mint_extension_data::<TransferFeeConfig>(&mint.to_account_info())
+    else {
+        return Ok(0);
+    };
+
+    let epoch = Clock::get()?.epoch;
+    fee_config
+        .calculate_epoch_fee(epoch, amount)
+        .ok_or_else(|| GhostPayrollError::ArithmeticOverflow.into())
+}
+
+/// Returns the gross amount that must be sent so the recipient is credited
+/// exactly `net_amount` after the mint's transfer fee is withheld.
+pub fn gross_up_for_fee(mint: &InterfaceAccount<Mint>, net_amount: u64) -> Result<u64> {
+    let Ok(fee_config) = get_mint_extension_data::<TransferFeeConfig>(&mint.to_account_info())
+    else {
+        return Ok(net_amount);
+    };
+
+    let epoch = Clock::get()?.epoch;
+    let fee = fee_config
+        .get_epoch_fee(epoch)
+        .calculate_inverse_fee(net_amount)
+        .ok_or(GhostPayrollError::ArithmeticOverflow)?;
+
+    net_amount
+        .checked_add(fee)
+        .ok_or_else(|| GhostPayrollError::ArithmeticOverflow.into())
+}
+
+/// Transfers `amount` out of a company treasury, signing with the company PDA
+pub fn transfer_from_treasury<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    treasury: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    destination: AccountInfo<'info>,
+    company: &Account<'info, Company>,
+    amount: u64,
+) -> Result<()> {
+    let company_seeds = &[
+        COMPANY_SEED,
+        company.authority.as_ref(),
+        &[company.bump],
+    ];
+    let signer_seeds = &[&company_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        TransferChecked {
+            from: treasury.to_account_info(),
+            mint: mint.to_account_info(),
+            to: destination,
+            authority: company.to_account_info(),
+        },
+        signer_seeds,
+    );
+
+    token_interface::transfer_checked(transfer_ctx, amount, mint.decimals)
+}
+
+/// Moves transfer fees withheld on `account` to the mint so the account can be closed.
+/// Does nothing for mints without the transfer fee extension.
+pub fn harvest_withheld_fees<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    account: AccountInfo<'info>,
+) -> Result<()> {
+    if get_mint_extension_data::<TransferFeeConfig>(&mint.to_account_info()).is_err() {
+        return Ok(());
+    }
+
+    let harvest_ctx = CpiContext::new(
+        token_program.to_account_info(),
+        HarvestWithheldTokensToMint {
+            token_program_id: token_program.to_account_info(),
+            mint: mint.to_account_info(),
+        },
+    );
+
+    harvest_withheld_tokens_to_mint(harvest_ctx, vec![account])
+}